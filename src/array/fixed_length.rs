@@ -0,0 +1,87 @@
+use {
+    serde::{
+        de::{
+            Deserialize, Deserializer, Error, IgnoredAny, SeqAccess, Visitor,
+        },
+        ser::{Serialize, SerializeTuple, Serializer},
+    },
+    std::{
+        convert::TryInto,
+        fmt::{self, Formatter},
+        marker::PhantomData,
+    },
+};
+
+/// Serialize an array as a fixed-length array.
+pub fn serialize<T, S, const N: usize>(
+    array: &[T; N],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    let mut tuple_serializer = serializer.serialize_tuple(N)?;
+
+    for element in array {
+        tuple_serializer.serialize_element(element)?;
+    }
+
+    tuple_serializer.end()
+}
+
+/// Deserialize a fixed-length array into an array with the same length.
+pub fn deserialize<'de, D, T, const N: usize>(
+    deserializer: D,
+) -> Result<[T; N], D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    deserializer.deserialize_tuple(N, ArrayVisitor::new())
+}
+
+struct ArrayVisitor<T, const N: usize> {
+    _element: PhantomData<T>,
+}
+
+impl<T, const N: usize> ArrayVisitor<T, N> {
+    pub fn new() -> Self {
+        ArrayVisitor {
+            _element: PhantomData,
+        }
+    }
+}
+
+impl<'de, T, const N: usize> Visitor<'de> for ArrayVisitor<T, N>
+where
+    T: Deserialize<'de>,
+{
+    type Value = [T; N];
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "fixed-length array of {} elements", N)
+    }
+
+    fn visit_seq<A>(self, mut sequence: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(N);
+
+        for index in 0..N {
+            match sequence.next_element()? {
+                Some(element) => elements.push(element),
+                None => return Err(A::Error::invalid_length(index, &self)),
+            }
+        }
+
+        if sequence.next_element::<IgnoredAny>()?.is_some() {
+            return Err(A::Error::invalid_length(N + 1, &self));
+        }
+
+        elements.try_into().map_err(|elements: Vec<T>| {
+            A::Error::invalid_length(elements.len(), &self)
+        })
+    }
+}
@@ -0,0 +1,45 @@
+/// Serialization and deserialization of an array as an XDR fixed-length array.
+///
+/// This module contains `serialize` and `deserialize` functions that can be
+/// specified to be used to serialize a field using the `with` serde attribute.
+/// Exactly `N` elements are serialized, without a length prefix, and
+/// deserialization fails if a different number of elements is found.
+///
+/// Serde only implements serialization for arrays of up to 32 elements, so
+/// this module can also be used for longer arrays.
+///
+/// # Examples
+///
+/// ```
+/// extern crate serde_xdr;
+/// #[macro_use]
+/// extern crate serde_derive;
+///
+/// use std::io::Cursor;
+///
+/// #[derive(Debug, Serialize, Deserialize, PartialEq)]
+/// struct Data {
+///     #[serde(with = "serde_xdr::array::fixed_length")]
+///     values: [i32; 3],
+/// }
+///
+/// fn main() {
+///     let data = Data { values: [1, -1, 2] };
+///
+///     let bytes = serde_xdr::to_bytes(&data).unwrap();
+///
+///     assert_eq!(
+///         bytes,
+///         vec![0, 0, 0, 1, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 2]
+///     );
+///
+///     let mut cursor = Cursor::new(bytes);
+///     let deserialized_data = serde_xdr::from_reader(&mut cursor).unwrap();
+///
+///     assert_eq!(data, deserialized_data);
+/// }
+/// ```
+pub mod fixed_length;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,145 @@
+use {
+    super::super::{
+        super::{from_reader, to_bytes},
+        fixed_length,
+    },
+    serde::de::value::{Error, SeqDeserializer},
+    std::io::Cursor,
+};
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct ThreeIntegers {
+    #[serde(with = "fixed_length")]
+    data: [i32; 3],
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct TwoStrings {
+    #[serde(with = "fixed_length")]
+    data: [String; 2],
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct FortyIntegers {
+    #[serde(with = "fixed_length")]
+    data: [u32; 40],
+}
+
+#[test]
+fn serialize_integers() {
+    let integers = ThreeIntegers { data: [1, -2, 3] };
+
+    let serialized_bytes = to_bytes(&integers).unwrap();
+
+    assert_eq!(
+        serialized_bytes,
+        vec![0, 0, 0, 1, 0xff, 0xff, 0xff, 0xfe, 0, 0, 0, 3]
+    );
+}
+
+#[test]
+fn serialize_strings() {
+    let strings = TwoStrings {
+        data: ["Hi!".to_string(), "Hey!".to_string()],
+    };
+
+    let serialized_bytes = to_bytes(&strings).unwrap();
+
+    assert_eq!(
+        serialized_bytes,
+        vec![
+            0, 0, 0, 3, b'H', b'i', b'!', 0, 0, 0, 0, 4, b'H', b'e', b'y',
+            b'!',
+        ]
+    );
+}
+
+#[test]
+fn serialize_array_longer_than_32_elements() {
+    let mut data = [0; 40];
+
+    for (index, element) in data.iter_mut().enumerate() {
+        *element = index as u32;
+    }
+
+    let serialized_bytes = to_bytes(&FortyIntegers { data }).unwrap();
+
+    assert_eq!(serialized_bytes.len(), 40 * 4);
+    assert_eq!(&serialized_bytes[156..], &[0, 0, 0, 39]);
+}
+
+#[test]
+fn deserialize_integers() {
+    let serialized_bytes = [0, 0, 0, 1, 0xff, 0xff, 0xff, 0xfe, 0, 0, 0, 3];
+    let mut cursor = Cursor::new(serialized_bytes);
+
+    let integers: ThreeIntegers = from_reader(&mut cursor).unwrap();
+
+    assert_eq!(integers.data, [1, -2, 3]);
+    assert_eq!(cursor.position(), 12);
+}
+
+#[test]
+fn deserialize_strings() {
+    let serialized_bytes = [
+        0, 0, 0, 3, b'H', b'i', b'!', 0, 0, 0, 0, 4, b'H', b'e', b'y', b'!',
+    ];
+    let mut cursor = Cursor::new(serialized_bytes);
+
+    let strings: TwoStrings = from_reader(&mut cursor).unwrap();
+
+    assert_eq!(strings.data, ["Hi!".to_string(), "Hey!".to_string()]);
+}
+
+#[test]
+fn serialize_deserialize_array_longer_than_32_elements() {
+    let mut data = [0; 40];
+
+    for (index, element) in data.iter_mut().enumerate() {
+        *element = 1000 + index as u32;
+    }
+
+    let integers = FortyIntegers { data };
+    let serialized_bytes = to_bytes(&integers).unwrap();
+    let mut cursor = Cursor::new(serialized_bytes);
+
+    let deserialized_integers: FortyIntegers =
+        from_reader(&mut cursor).unwrap();
+
+    assert_eq!(deserialized_integers, integers);
+}
+
+#[test]
+fn deserialize_too_few_elements() {
+    let serialized_bytes = [0, 0, 0, 1, 0xff, 0xff, 0xff, 0xfe];
+    let mut cursor = Cursor::new(serialized_bytes);
+
+    let result: Result<ThreeIntegers, _> = from_reader(&mut cursor);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn deserialize_too_many_elements() {
+    let elements =
+        SeqDeserializer::<_, Error>::new(vec![1, 2, 3, 4].into_iter());
+
+    let result: Result<[i32; 3], _> = fixed_length::deserialize(elements);
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "invalid length 4, expected fixed-length array of 3 elements"
+    );
+}
+
+#[test]
+fn deserialize_too_few_elements_from_sequence() {
+    let elements = SeqDeserializer::<_, Error>::new(vec![1, 2].into_iter());
+
+    let result: Result<[i32; 3], _> = fixed_length::deserialize(elements);
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "invalid length 2, expected fixed-length array of 3 elements"
+    );
+}
@@ -0,0 +1 @@
+mod fixed_length;
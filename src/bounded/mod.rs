@@ -0,0 +1,101 @@
+use {
+    serde::{
+        de::{Deserialize, Deserializer, Error, SeqAccess, Visitor},
+        ser::{Serialize, SerializeTupleStruct, Serializer},
+    },
+    std::{
+        fmt::{self, Formatter},
+        marker::PhantomData,
+    },
+};
+
+pub use self::{string::XdrString, var_array::VarArray, var_opaque::VarOpaque};
+
+/// Name used to identify bounded values to the XDR serializer and
+/// deserializer, so that their maximum length is enforced.
+pub(crate) const TYPE_NAME: &str = "serde_xdr::bounded";
+
+trait Length {
+    fn length(&self) -> usize;
+}
+
+fn serialize<T, S>(
+    value: &T,
+    maximum: u32,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    let mut tuple_struct_serializer =
+        serializer.serialize_tuple_struct(TYPE_NAME, maximum as usize)?;
+
+    tuple_struct_serializer.serialize_field(value)?;
+    tuple_struct_serializer.end()
+}
+
+fn deserialize<'de, T, D>(maximum: u32, deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de> + Length,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_tuple_struct(
+        TYPE_NAME,
+        maximum as usize,
+        BoundedVisitor::new(maximum),
+    )
+}
+
+struct BoundedVisitor<T> {
+    maximum: u32,
+    _value: PhantomData<T>,
+}
+
+impl<T> BoundedVisitor<T> {
+    pub fn new(maximum: u32) -> Self {
+        BoundedVisitor {
+            maximum,
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> Visitor<'de> for BoundedVisitor<T>
+where
+    T: Deserialize<'de> + Length,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a value with a maximum length of {}",
+            self.maximum
+        )
+    }
+
+    fn visit_seq<A>(self, mut sequence: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let value: T = sequence
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+
+        let length = value.length();
+
+        if length > self.maximum as usize {
+            return Err(A::Error::invalid_length(length, &self));
+        }
+
+        Ok(value)
+    }
+}
+
+mod string;
+mod var_array;
+mod var_opaque;
+
+#[cfg(test)]
+mod tests;
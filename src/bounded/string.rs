@@ -0,0 +1,70 @@
+use {
+    super::Length,
+    serde::{Deserialize, Deserializer, Serialize, Serializer},
+    std::ops::{Deref, DerefMut},
+};
+
+/// A string with at most `MAX` bytes.
+///
+/// Equivalent to the XDR declaration `string identifier<MAX>`. Serialization
+/// fails if the string is longer than `MAX` bytes, and deserialization fails
+/// before reading the string if the length prefix is larger than `MAX`.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct XdrString<const MAX: u32>(pub String);
+
+impl<const MAX: u32> XdrString<MAX> {
+    /// Unwrap the string.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<const MAX: u32> From<String> for XdrString<MAX> {
+    fn from(string: String) -> Self {
+        XdrString(string)
+    }
+}
+
+impl<'a, const MAX: u32> From<&'a str> for XdrString<MAX> {
+    fn from(string: &'a str) -> Self {
+        XdrString(string.to_string())
+    }
+}
+
+impl<const MAX: u32> Deref for XdrString<MAX> {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const MAX: u32> DerefMut for XdrString<MAX> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<const MAX: u32> Serialize for XdrString<MAX> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize(&self.0, MAX, serializer)
+    }
+}
+
+impl<'de, const MAX: u32> Deserialize<'de> for XdrString<MAX> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize(MAX, deserializer).map(XdrString)
+    }
+}
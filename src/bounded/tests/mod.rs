@@ -0,0 +1,3 @@
+mod string;
+mod var_array;
+mod var_opaque;
@@ -0,0 +1,62 @@
+use {
+    super::super::{
+        super::{
+            from_reader, to_bytes, DeserializationError, SerializationError,
+        },
+        XdrString,
+    },
+    std::io::Cursor,
+};
+
+#[test]
+fn serialize_string_within_bound() {
+    let name: XdrString<5> = "Hello".into();
+
+    let bytes = to_bytes(&name).unwrap();
+
+    assert_eq!(
+        bytes,
+        vec![0, 0, 0, 5, b'H', b'e', b'l', b'l', b'o', 0, 0, 0]
+    );
+}
+
+#[test]
+fn serialize_string_exceeding_bound() {
+    let name: XdrString<4> = "Hello".into();
+
+    let error = to_bytes(&name).unwrap_err();
+
+    match SerializationError::from(error).root_cause() {
+        SerializationError::LengthExceedsMaximum { length, maximum } => {
+            assert_eq!(*length, 5);
+            assert_eq!(*maximum, 4);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn deserialize_string_within_bound() {
+    let mut cursor = Cursor::new(vec![0, 0, 0, 3, b'H', b'i', b'!', 0]);
+
+    let name: XdrString<3> = from_reader(&mut cursor).unwrap();
+
+    assert_eq!(*name, "Hi!");
+}
+
+#[test]
+fn deserialize_string_exceeding_bound() {
+    let mut cursor = Cursor::new(vec![0, 0, 0, 3, b'H', b'i', b'!', 0]);
+
+    let error = from_reader::<_, XdrString<2>>(&mut cursor).unwrap_err();
+
+    match DeserializationError::from(error).root_cause() {
+        DeserializationError::LengthExceedsMaximum { length, maximum } => {
+            assert_eq!(*length, 3);
+            assert_eq!(*maximum, 2);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+
+    assert_eq!(cursor.position(), 4);
+}
@@ -0,0 +1,93 @@
+use {
+    super::super::{
+        super::{
+            from_reader, to_bytes, DeserializationError, SerializationError,
+        },
+        VarArray,
+    },
+    std::io::Cursor,
+};
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Identifiers {
+    ids: VarArray<i32, 3>,
+}
+
+#[test]
+fn serialize_array_within_bound() {
+    let identifiers = Identifiers {
+        ids: vec![1, -1, 2].into(),
+    };
+
+    let bytes = to_bytes(&identifiers).unwrap();
+
+    assert_eq!(
+        bytes,
+        vec![0, 0, 0, 3, 0, 0, 0, 1, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 2]
+    );
+}
+
+#[test]
+fn serialize_array_exceeding_bound() {
+    let identifiers = Identifiers {
+        ids: vec![1, 2, 3, 4].into(),
+    };
+
+    let error = to_bytes(&identifiers).unwrap_err();
+
+    match SerializationError::from(error).root_cause() {
+        SerializationError::LengthExceedsMaximum { length, maximum } => {
+            assert_eq!(*length, 4);
+            assert_eq!(*maximum, 3);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn deserialize_array_within_bound() {
+    let mut cursor = Cursor::new(vec![0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2]);
+
+    let identifiers: Identifiers = from_reader(&mut cursor).unwrap();
+
+    assert_eq!(identifiers.ids.into_inner(), vec![1, 2]);
+    assert_eq!(cursor.position(), 12);
+}
+
+#[test]
+fn deserialize_array_exceeding_bound() {
+    let mut cursor = Cursor::new(vec![0xff, 0xff, 0xff, 0xff, 0, 0, 0, 1]);
+
+    let error = from_reader::<_, Identifiers>(&mut cursor).unwrap_err();
+
+    match DeserializationError::from(error).root_cause() {
+        DeserializationError::LengthExceedsMaximum { length, maximum } => {
+            assert_eq!(*length, u32::MAX);
+            assert_eq!(*maximum, 3);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+
+    assert_eq!(cursor.position(), 4);
+}
+
+#[test]
+fn bound_does_not_apply_to_following_fields() {
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Lists {
+        bounded: VarArray<u8, 1>,
+        unbounded: Vec<u8>,
+    }
+
+    let lists = Lists {
+        bounded: vec![1].into(),
+        unbounded: vec![2, 3],
+    };
+
+    let bytes = to_bytes(&lists).unwrap();
+    let mut cursor = Cursor::new(bytes);
+
+    let deserialized_lists: Lists = from_reader(&mut cursor).unwrap();
+
+    assert_eq!(deserialized_lists, lists);
+}
@@ -0,0 +1,60 @@
+use {
+    super::super::{
+        super::{
+            from_reader, to_bytes, DeserializationError, SerializationError,
+        },
+        VarOpaque,
+    },
+    std::io::Cursor,
+};
+
+#[test]
+fn serialize_opaque_within_bound() {
+    let data: VarOpaque<8> = vec![1, 2, 3, 4, 5].into();
+
+    let bytes = to_bytes(&data).unwrap();
+
+    assert_eq!(bytes, vec![0, 0, 0, 5, 1, 2, 3, 4, 5, 0, 0, 0]);
+}
+
+#[test]
+fn serialize_opaque_exceeding_bound() {
+    let data: VarOpaque<4> = vec![1, 2, 3, 4, 5].into();
+
+    let error = to_bytes(&data).unwrap_err();
+
+    match SerializationError::from(error).root_cause() {
+        SerializationError::LengthExceedsMaximum { length, maximum } => {
+            assert_eq!(*length, 5);
+            assert_eq!(*maximum, 4);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn deserialize_opaque_within_bound() {
+    let mut cursor = Cursor::new(vec![0, 0, 0, 2, 0xab, 0xcd, 0, 0]);
+
+    let data: VarOpaque<2> = from_reader(&mut cursor).unwrap();
+
+    assert_eq!(data.into_inner(), vec![0xab, 0xcd]);
+    assert_eq!(cursor.position(), 8);
+}
+
+#[test]
+fn deserialize_opaque_exceeding_bound() {
+    let mut cursor = Cursor::new(vec![0x80, 0, 0, 0]);
+
+    let error = from_reader::<_, VarOpaque<16>>(&mut cursor).unwrap_err();
+
+    match DeserializationError::from(error).root_cause() {
+        DeserializationError::LengthExceedsMaximum { length, maximum } => {
+            assert_eq!(*length, 0x8000_0000);
+            assert_eq!(*maximum, 16);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+
+    assert_eq!(cursor.position(), 4);
+}
@@ -0,0 +1,90 @@
+use {
+    super::Length,
+    serde::{Deserialize, Deserializer, Serialize, Serializer},
+    std::ops::{Deref, DerefMut},
+};
+
+/// A variable-length array with at most `MAX` elements.
+///
+/// Equivalent to the XDR declaration `T identifier<MAX>`. Serialization fails
+/// if the array has more than `MAX` elements, and deserialization fails
+/// before reading any elements if the length prefix is larger than `MAX`.
+///
+/// # Examples
+///
+/// ```
+/// extern crate serde_xdr;
+///
+/// use serde_xdr::bounded::VarArray;
+///
+/// fn main() {
+///     let ids: VarArray<u32, 2> = vec![7, 8].into();
+///
+///     let bytes = serde_xdr::to_bytes(&ids).unwrap();
+///
+///     assert_eq!(bytes, vec![0, 0, 0, 2, 0, 0, 0, 7, 0, 0, 0, 8]);
+///
+///     let too_many_ids: VarArray<u32, 2> = vec![7, 8, 9].into();
+///
+///     assert!(serde_xdr::to_bytes(&too_many_ids).is_err());
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct VarArray<T, const MAX: u32>(pub Vec<T>);
+
+impl<T, const MAX: u32> VarArray<T, MAX> {
+    /// Unwrap the array into its elements.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const MAX: u32> From<Vec<T>> for VarArray<T, MAX> {
+    fn from(elements: Vec<T>) -> Self {
+        VarArray(elements)
+    }
+}
+
+impl<T, const MAX: u32> Deref for VarArray<T, MAX> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const MAX: u32> DerefMut for VarArray<T, MAX> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, const MAX: u32> Serialize for VarArray<T, MAX>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize(&self.0, MAX, serializer)
+    }
+}
+
+impl<'de, T, const MAX: u32> Deserialize<'de> for VarArray<T, MAX>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize(MAX, deserializer).map(VarArray)
+    }
+}
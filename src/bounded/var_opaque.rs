@@ -0,0 +1,67 @@
+use {
+    super::Length,
+    crate::opaque_data::{ByteBuf, Bytes},
+    serde::{Deserialize, Deserializer, Serialize, Serializer},
+    std::ops::{Deref, DerefMut},
+};
+
+/// Variable-length opaque data with at most `MAX` bytes.
+///
+/// Equivalent to the XDR declaration `opaque identifier<MAX>`. Serialization
+/// fails if there are more than `MAX` bytes, and deserialization fails before
+/// reading the data if the length prefix is larger than `MAX`.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct VarOpaque<const MAX: u32>(pub Vec<u8>);
+
+impl<const MAX: u32> VarOpaque<MAX> {
+    /// Unwrap the opaque data into its bytes.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl<const MAX: u32> From<Vec<u8>> for VarOpaque<MAX> {
+    fn from(bytes: Vec<u8>) -> Self {
+        VarOpaque(bytes)
+    }
+}
+
+impl<const MAX: u32> Deref for VarOpaque<MAX> {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const MAX: u32> DerefMut for VarOpaque<MAX> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Length for ByteBuf {
+    fn length(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<const MAX: u32> Serialize for VarOpaque<MAX> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize(&Bytes(&self.0), MAX, serializer)
+    }
+}
+
+impl<'de, const MAX: u32> Deserialize<'de> for VarOpaque<MAX> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let ByteBuf(bytes) = super::deserialize(MAX, deserializer)?;
+
+        Ok(VarOpaque(bytes))
+    }
+}
@@ -4,9 +4,8 @@ use {
         super::errors::{
             CompatDeserializationError, DeserializationError, Result,
         },
-        Deserializer,
+        Deserializer, XdrRead,
     },
-    byteorder::ReadBytesExt,
     serde::de::{
         value::U32Deserializer, DeserializeSeed, EnumAccess, IntoDeserializer,
     },
@@ -15,7 +14,7 @@ use {
 pub struct EnumDeserializer<'a, 'r, R>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: ?Sized + 'r,
 {
     enum_name: &'static str,
     variant: u32,
@@ -26,7 +25,7 @@ where
 impl<'a, 'r, R> EnumDeserializer<'a, 'r, R>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: ?Sized + 'r,
 {
     pub fn new(
         enum_name: &'static str,
@@ -46,7 +45,7 @@ where
 impl<'a, 'de, 'r, R> EnumAccess<'de> for EnumDeserializer<'a, 'r, R>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: XdrRead<'de> + ?Sized + 'r,
 {
     type Error = CompatDeserializationError;
     type Variant = VariantDeserializer<'a, 'r, R>;
@@ -62,7 +62,6 @@ fn deserialize_third_enum_variant() {
 }
 
 #[test]
-#[cfg(not(feature = "ignore-enum-variant-names"))]
 fn deserialize_inexistent_enum_variant() {
     static VARIANT_NAMES: [&str; 3] = ["bool", "string", "integer"];
 
@@ -81,3 +80,18 @@ fn deserialize_inexistent_enum_variant() {
     assert_eq!(cursor.position(), 4);
     assert_eq!(result.to_string(), expected_error);
 }
+
+#[test]
+fn deserialize_inexistent_enum_variant_with_ignored_names() {
+    let mut cursor = Cursor::new(vec![0x00, 0x00, 0x00, 0x03]);
+
+    let result = Deserializer::new(&mut cursor)
+        .with_ignored_enum_variant_names(true)
+        .deserialize_enum("enum", &[], Visitor)
+        .unwrap();
+
+    let expected_result = Value::Enum(3, Box::new(Value::Nothing));
+
+    assert_eq!(cursor.position(), 4);
+    assert_eq!(result, expected_result);
+}
@@ -1,9 +1,8 @@
 use {
     super::{
         super::super::errors::{CompatDeserializationError, Result},
-        deserialize_enum_error, Deserializer,
+        deserialize_enum_error, Deserializer, XdrRead,
     },
-    byteorder::ReadBytesExt,
     serde::{
         de::{DeserializeSeed, VariantAccess, Visitor},
         Deserializer as _,
@@ -13,7 +12,7 @@ use {
 pub struct VariantDeserializer<'a, 'r, R>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: ?Sized + 'r,
 {
     enum_name: &'static str,
     variant_name: &'static str,
@@ -23,7 +22,7 @@ where
 impl<'a, 'r, R> VariantDeserializer<'a, 'r, R>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: ?Sized + 'r,
 {
     pub fn new(
         enum_name: &'static str,
@@ -41,7 +40,7 @@ where
 impl<'a, 'de, 'r, R> VariantAccess<'de> for VariantDeserializer<'a, 'r, R>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: XdrRead<'de> + ?Sized + 'r,
 {
     type Error = CompatDeserializationError;
 
@@ -0,0 +1,102 @@
+use {
+    super::super::{
+        errors::{CompatDeserializationError, DeserializationError, Result},
+        Deserializer, XdrRead,
+    },
+    crate::PathSegment,
+    serde::de::{DeserializeSeed, MapAccess},
+};
+
+pub struct MapDeserializer<'a, 'r, R>
+where
+    'r: 'a,
+    R: ?Sized + 'r,
+{
+    length: u32,
+    current_index: u32,
+    deserializer: &'a mut Deserializer<'r, R>,
+}
+
+impl<'a, 'r, R> MapDeserializer<'a, 'r, R>
+where
+    'r: 'a,
+    R: ?Sized + 'r,
+{
+    pub fn new(length: u32, deserializer: &'a mut Deserializer<'r, R>) -> Self {
+        MapDeserializer {
+            length,
+            current_index: 0,
+            deserializer,
+        }
+    }
+}
+
+impl<'a, 'de, 'r, R> MapAccess<'de> for MapDeserializer<'a, 'r, R>
+where
+    'r: 'a,
+    R: XdrRead<'de> + ?Sized + 'r,
+{
+    type Error = CompatDeserializationError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.current_index < self.length {
+            let offset = self.deserializer.bytes_read;
+            let key =
+                seed.deserialize(&mut *self.deserializer).map_err(|error| {
+                    let index = self.current_index;
+                    let segment = PathSegment::Key(index as usize);
+
+                    deserialize_error("key", index, segment, offset, error)
+                })?;
+
+            Ok(Some(key))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let offset = self.deserializer.bytes_read;
+        let value =
+            seed.deserialize(&mut *self.deserializer).map_err(|error| {
+                let index = self.current_index;
+                let segment = PathSegment::Value(index as usize);
+
+                deserialize_error("value", index, segment, offset, error)
+            })?;
+
+        self.current_index += 1;
+
+        Ok(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        let remaining = self.length - self.current_index;
+
+        Some(self.deserializer.size_hint(remaining))
+    }
+}
+
+fn deserialize_error(
+    part: &str,
+    index: u32,
+    segment: PathSegment,
+    offset: u64,
+    cause: CompatDeserializationError,
+) -> DeserializationError {
+    DeserializationError::failure_at(
+        format!("{} of map entry {}", part, index),
+        segment,
+        offset,
+        cause,
+    )
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,62 @@
+use {
+    super::super::super::{DeserializationError, Deserializer},
+    crate::MapEncoding,
+    serde::Deserialize,
+    std::{collections::BTreeMap, io::Cursor},
+};
+
+#[test]
+fn map_is_not_supported_by_default() {
+    let mut cursor = Cursor::new(vec![0x00, 0x00, 0x00, 0x00]);
+    let mut deserializer = Deserializer::new(&mut cursor);
+
+    let result = BTreeMap::<u32, u32>::deserialize(&mut deserializer)
+        .map_err(DeserializationError::from);
+
+    match result {
+        Err(DeserializationError::MapIsNotSupported) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn deserialize_map() {
+    let mut cursor = Cursor::new(vec![
+        0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02,
+        b'H', b'i', 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x03,
+        b'H', b'e', b'y', 0x00,
+    ]);
+
+    let mut deserializer =
+        Deserializer::new(&mut cursor).with_map_encoding(MapEncoding::Unsorted);
+
+    let result = BTreeMap::<u32, String>::deserialize(&mut deserializer);
+
+    let mut expected_result = BTreeMap::new();
+
+    expected_result.insert(1, "Hey".to_string());
+    expected_result.insert(2, "Hi".to_string());
+
+    assert_eq!(result.unwrap(), expected_result);
+    assert_eq!(cursor.position(), 28);
+}
+
+#[test]
+fn deserialize_map_with_unsorted_entries_in_sorted_mode() {
+    let mut cursor = Cursor::new(vec![
+        0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03,
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x04,
+    ]);
+
+    let mut deserializer =
+        Deserializer::new(&mut cursor).with_map_encoding(MapEncoding::Sorted);
+
+    let result = BTreeMap::<u32, u32>::deserialize(&mut deserializer);
+
+    let mut expected_result = BTreeMap::new();
+
+    expected_result.insert(1, 4);
+    expected_result.insert(2, 3);
+
+    assert_eq!(result.unwrap(), expected_result);
+}
@@ -1,15 +1,15 @@
 use {
     self::{
-        enum_deserializer::EnumDeserializer,
+        enum_deserializer::EnumDeserializer, map_deserializer::MapDeserializer,
         struct_deserializer::StructDeserializer,
     },
     super::{
         errors::{CompatDeserializationError, DeserializationError, Result},
-        Deserializer,
+        Deserializer, XdrRead,
     },
-    byteorder::{BigEndian, ReadBytesExt},
+    crate::{bounded, opaque_data::fixed_length, MapEncoding, StringEncoding},
     serde::de::{self, Visitor},
-    std::char,
+    std::{borrow::Cow, char, str},
 };
 
 pub use self::sequence_deserializer::SequenceDeserializer;
@@ -17,7 +17,7 @@ pub use self::sequence_deserializer::SequenceDeserializer;
 impl<'a, 'de, 'r, R> de::Deserializer<'de> for &'a mut Deserializer<'r, R>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: XdrRead<'de> + ?Sized + 'r,
 {
     type Error = CompatDeserializationError;
 
@@ -32,10 +32,7 @@ where
     where
         V: Visitor<'de>,
     {
-        let value = self
-            .reader
-            .read_u32::<BigEndian>()
-            .map_err(|error| DeserializationError::io_error("bool", error))?;
+        let value = self.read_array("bool").map(u32::from_be_bytes)?;
 
         match value {
             0 => visitor.visit_bool(false),
@@ -75,9 +72,9 @@ where
     where
         V: Visitor<'de>,
     {
-        let value = self.reader.read_i64::<BigEndian>().map_err(|error| {
-            DeserializationError::io_error("signed 64-bit integer", error)
-        })?;
+        let value = self
+            .read_array("signed 64-bit integer")
+            .map(i64::from_be_bytes)?;
 
         visitor.visit_i64(value)
     }
@@ -113,9 +110,9 @@ where
     where
         V: Visitor<'de>,
     {
-        let value = self.reader.read_u64::<BigEndian>().map_err(|error| {
-            DeserializationError::io_error("unsigned 64-bit integer", error)
-        })?;
+        let value = self
+            .read_array("unsigned 64-bit integer")
+            .map(u64::from_be_bytes)?;
 
         visitor.visit_u64(value)
     }
@@ -124,10 +121,7 @@ where
     where
         V: Visitor<'de>,
     {
-        let value = self
-            .reader
-            .read_f32::<BigEndian>()
-            .map_err(|error| DeserializationError::io_error("float", error))?;
+        let value = self.read_array("float").map(f32::from_be_bytes)?;
 
         visitor.visit_f32(value)
     }
@@ -136,10 +130,7 @@ where
     where
         V: Visitor<'de>,
     {
-        let value = self
-            .reader
-            .read_f64::<BigEndian>()
-            .map_err(|error| DeserializationError::io_error("double", error))?;
+        let value = self.read_array("double").map(f64::from_be_bytes)?;
 
         visitor.visit_f64(value)
     }
@@ -148,15 +139,18 @@ where
     where
         V: Visitor<'de>,
     {
-        let raw_value = self
-            .reader
-            .read_u32::<BigEndian>()
-            .map_err(|error| DeserializationError::io_error("char", error))?;
+        let raw_value = self.read_array("char").map(u32::from_be_bytes)?;
+
+        let value = match self.string_encoding {
+            Some(StringEncoding::Ascii) if raw_value > 0x7f => None,
+            Some(StringEncoding::Latin1) if raw_value > 0xff => None,
+            _ => char::from_u32(raw_value),
+        };
 
-        let value = char::from_u32(raw_value)
+        let value = value
             .ok_or_else(|| DeserializationError::InvalidChar { raw_value })?;
 
-        visitor.visit_char(value as char)
+        visitor.visit_char(value)
     }
 
     fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
@@ -164,7 +158,31 @@ where
         V: Visitor<'de>,
     {
         let buffer = self.deserialize_opaque("string")?;
-        let string = String::from_utf8(buffer)
+
+        match self.string_encoding {
+            Some(StringEncoding::Ascii) => ensure!(
+                buffer.is_ascii(),
+                DeserializationError::InvalidAsciiString {
+                    bytes: buffer.into_owned(),
+                }
+            ),
+            Some(StringEncoding::Latin1) => {
+                let string = buffer.iter().map(|&byte| char::from(byte));
+
+                return visitor.visit_string(string.collect());
+            }
+            Some(StringEncoding::Utf8) | None => {}
+        }
+
+        let string = match buffer {
+            Cow::Borrowed(bytes) => match str::from_utf8(bytes) {
+                Ok(string) => return visitor.visit_borrowed_str(string),
+                Err(_) => String::from_utf8(bytes.to_vec()),
+            },
+            Cow::Owned(bytes) => String::from_utf8(bytes),
+        };
+
+        let string = string
             .map_err(|cause| DeserializationError::InvalidString { cause })?;
 
         visitor.visit_string(string)
@@ -181,9 +199,10 @@ where
     where
         V: Visitor<'de>,
     {
-        let buffer = self.deserialize_opaque("opaque")?;
-
-        visitor.visit_byte_buf(buffer)
+        match self.deserialize_opaque("opaque")? {
+            Cow::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
+        }
     }
 
     fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
@@ -197,14 +216,11 @@ where
     where
         V: Visitor<'de>,
     {
-        let option = self
-            .reader
-            .read_i32::<BigEndian>()
-            .map_err(|error| DeserializationError::io_error("option", error))?;
+        let option = self.read_array("option").map(i32::from_be_bytes)?;
 
         let result = match option {
             0 => visitor.visit_none(),
-            1 => visitor.visit_some(self),
+            1 => self.nested(|deserializer| visitor.visit_some(deserializer)),
             _ => bail!(DeserializationError::InvalidOption),
         };
 
@@ -249,11 +265,9 @@ where
     where
         V: Visitor<'de>,
     {
-        let length = self.reader.read_u32::<BigEndian>().map_err(|error| {
-            DeserializationError::io_error("sequence", error)
-        })?;
+        let length = self.deserialize_length("sequence")?;
 
-        self.deserialize_sequence(visitor, "sequence", length as u32)
+        self.deserialize_sequence(visitor, "sequence", length)
     }
 
     fn deserialize_tuple<V>(self, length: usize, visitor: V) -> Result<V::Value>
@@ -276,20 +290,59 @@ where
     where
         V: Visitor<'de>,
     {
+        if name == bounded::TYPE_NAME {
+            self.length_bound = Some(length.min(u32::MAX as usize) as u32);
+
+            // The bounded value is a wrapper, so its single element isn't
+            // part of the path to the values it contains
+            let result = self.nested(|deserializer| {
+                visitor.visit_seq(
+                    SequenceDeserializer::new(
+                        1,
+                        &"bounded value",
+                        deserializer,
+                    )
+                    .unindexed(),
+                )
+            });
+
+            self.length_bound = None;
+
+            return result;
+        }
+
         if length > u32::max_value() as usize {
             bail!(DeserializationError::TupleHasTooManyElements { length });
         }
 
+        if name == fixed_length::TYPE_NAME {
+            let buffer = self
+                .deserialize_opaque_contents(length, "fixed-length opaque")?;
+
+            return match buffer {
+                Cow::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+                Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
+            };
+        }
+
         let type_name = format!("tuple struct {}", name);
 
         self.deserialize_sequence(visitor, type_name, length as u32)
     }
 
-    fn deserialize_map<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        bail!(DeserializationError::MapIsNotSupported);
+        if self.map_encoding == MapEncoding::Disabled {
+            bail!(DeserializationError::MapIsNotSupported);
+        }
+
+        let length = self.deserialize_length("map")?;
+
+        self.nested(|deserializer| {
+            visitor.visit_map(MapDeserializer::new(length, deserializer))
+        })
     }
 
     fn deserialize_struct<V>(
@@ -301,9 +354,13 @@ where
     where
         V: Visitor<'de>,
     {
-        let struct_deserializer = StructDeserializer::new(name, fields, self);
-
-        visitor.visit_seq(struct_deserializer)
+        self.nested(|deserializer| {
+            visitor.visit_seq(StructDeserializer::new(
+                name,
+                fields,
+                deserializer,
+            ))
+        })
     }
 
     fn deserialize_enum<V>(
@@ -315,30 +372,26 @@ where
     where
         V: Visitor<'de>,
     {
-        let variant = self.reader.read_u32::<BigEndian>().map_err(|error| {
-            DeserializationError::io_error(format!("enum {}", name), error)
-        })?;
-
-        let variant_name = {
-            #[cfg(feature = "ignore-enum-variant-names")]
-            {
-                ""
-            }
-            #[cfg(not(feature = "ignore-enum-variant-names"))]
-            {
-                variants.get(variant as usize).ok_or_else(|| {
-                    DeserializationError::InvalidEnumVariant {
-                        variant,
-                        variants,
-                    }
-                })?
-            }
+        let variant = self
+            .read_array(format_args!("enum {}", name))
+            .map(u32::from_be_bytes)?;
+
+        let variant_name = if self.ignore_enum_variant_names {
+            ""
+        } else {
+            variants.get(variant as usize).ok_or_else(|| {
+                DeserializationError::InvalidEnumVariant { variant, variants }
+            })?
         };
 
-        let enum_deserializer =
-            EnumDeserializer::new(name, variant, variant_name, self);
-
-        visitor.visit_enum(enum_deserializer)
+        self.nested(|deserializer| {
+            visitor.visit_enum(EnumDeserializer::new(
+                name,
+                variant,
+                variant_name,
+                deserializer,
+            ))
+        })
     }
 
     fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value>
@@ -357,6 +410,7 @@ where
 }
 
 mod enum_deserializer;
+mod map_deserializer;
 mod sequence_deserializer;
 mod struct_deserializer;
 
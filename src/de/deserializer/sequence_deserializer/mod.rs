@@ -1,28 +1,29 @@
 use {
     super::super::{
         errors::{CompatDeserializationError, DeserializationError, Result},
-        Deserializer,
+        Deserializer, XdrRead,
     },
-    byteorder::ReadBytesExt,
+    crate::PathSegment,
     serde::de::{DeserializeSeed, SeqAccess},
 };
 
 pub struct SequenceDeserializer<'a, 'r, 's, R, S>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: ?Sized + 'r,
     S: ToString + 's,
 {
     length: u32,
     type_name: &'s S,
     current_index: u32,
+    indexed: bool,
     deserializer: &'a mut Deserializer<'r, R>,
 }
 
 impl<'a, 'r, 's, R, S> SequenceDeserializer<'a, 'r, 's, R, S>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: ?Sized + 'r,
     S: ToString,
 {
     pub fn new(
@@ -35,15 +36,25 @@ where
             type_name,
             deserializer,
             current_index: 0,
+            indexed: true,
         }
     }
+
+    /// Don't include the indices of the elements in the paths of errors.
+    ///
+    /// Used for wrappers like bounded values, whose single element is the
+    /// wrapped value itself.
+    pub fn unindexed(mut self) -> Self {
+        self.indexed = false;
+        self
+    }
 }
 
 impl<'a, 'de, 'r, 's, R, S> SeqAccess<'de>
     for SequenceDeserializer<'a, 'r, 's, R, S>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: XdrRead<'de> + ?Sized + 'r,
     S: ToString,
 {
     type Error = CompatDeserializationError;
@@ -53,9 +64,23 @@ where
         T: DeserializeSeed<'de>,
     {
         if self.current_index < self.length {
+            let offset = self.deserializer.bytes_read;
             let value =
                 seed.deserialize(&mut *self.deserializer).map_err(|error| {
-                    deserialize_error(self.type_name, self.current_index, error)
+                    let index = self.current_index;
+                    let type_name = self.type_name.to_string();
+                    let what =
+                        format!("element {} of type {}", index, type_name);
+
+                    if self.indexed {
+                        let segment = PathSegment::Element(index as usize);
+
+                        DeserializationError::failure_at(
+                            what, segment, offset, error,
+                        )
+                    } else {
+                        DeserializationError::failure(what, error)
+                    }
                 })?;
 
             self.current_index += 1;
@@ -67,22 +92,10 @@ where
     }
 
     fn size_hint(&self) -> Option<usize> {
-        Some(self.length as usize)
-    }
-}
+        let remaining = self.length - self.current_index;
 
-fn deserialize_error<S>(
-    type_name: &S,
-    index: u32,
-    cause: CompatDeserializationError,
-) -> DeserializationError
-where
-    S: ToString,
-{
-    DeserializationError::failure(
-        format!("element {} of type {}", index, type_name.to_string()),
-        cause,
-    )
+        Some(self.deserializer.size_hint(remaining))
+    }
 }
 
 #[cfg(test)]
@@ -1,12 +1,36 @@
 use {
     super::super::{
+        super::Limits,
         tests::{Value, Visitor},
         Deserializer,
     },
-    serde::Deserializer as _,
-    std::io::Cursor,
+    serde::{
+        de::{self, SeqAccess},
+        Deserializer as _,
+    },
+    std::{
+        fmt::{self, Formatter},
+        io::Cursor,
+    },
 };
 
+struct SizeHintVisitor;
+
+impl<'de> de::Visitor<'de> for SizeHintVisitor {
+    type Value = Option<usize>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "a sequence")
+    }
+
+    fn visit_seq<A>(self, sequence: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Ok(sequence.size_hint())
+    }
+}
+
 #[test]
 fn deserialize_sequence() {
     let mut cursor = Cursor::new(vec![
@@ -48,3 +72,16 @@ fn deserialize_tuple() {
     assert_eq!(cursor.position(), 16);
     assert_eq!(result, expected_result);
 }
+
+#[test]
+fn size_hint_is_bounded_by_allocation_limit() {
+    let mut cursor = Cursor::new(vec![0xff, 0xff, 0xff, 0xff]);
+    let limits = Limits::default().with_max_allocation(64);
+
+    let size_hint = Deserializer::new(&mut cursor)
+        .with_limits(limits)
+        .deserialize_seq(SizeHintVisitor)
+        .unwrap();
+
+    assert_eq!(size_hint, Some(64));
+}
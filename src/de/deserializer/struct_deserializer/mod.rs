@@ -1,16 +1,16 @@
 use {
     super::super::{
         errors::{CompatDeserializationError, DeserializationError, Result},
-        Deserializer,
+        Deserializer, XdrRead,
     },
-    byteorder::ReadBytesExt,
+    crate::PathSegment,
     serde::de::{DeserializeSeed, SeqAccess},
 };
 
 pub struct StructDeserializer<'a, 'r, R>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: ?Sized + 'r,
 {
     name: &'static str,
     fields: &'static [&'static str],
@@ -21,7 +21,7 @@ where
 impl<'a, 'r, R> StructDeserializer<'a, 'r, R>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: ?Sized + 'r,
 {
     pub fn new(
         name: &'static str,
@@ -40,7 +40,7 @@ where
 impl<'a, 'de, 'r, R> SeqAccess<'de> for StructDeserializer<'a, 'r, R>
 where
     'r: 'a,
-    R: ReadBytesExt + 'r,
+    R: XdrRead<'de> + ?Sized + 'r,
 {
     type Error = CompatDeserializationError;
 
@@ -48,13 +48,24 @@ where
     where
         T: DeserializeSeed<'de>,
     {
+        if self.current_field >= self.fields.len() {
+            return Ok(None);
+        }
+
+        let offset = self.deserializer.bytes_read;
         let value =
             seed.deserialize(&mut *self.deserializer).map_err(|error| {
                 let struct_name = self.name;
                 let field_name = self.fields[self.current_field];
+                let segment = PathSegment::Field {
+                    structure: struct_name.to_string(),
+                    field: field_name,
+                };
 
-                DeserializationError::failure(
+                DeserializationError::failure_at(
                     format!("struct field {}::{}", struct_name, field_name),
+                    segment,
+                    offset,
                     error,
                 )
             })?;
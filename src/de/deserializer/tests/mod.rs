@@ -1,7 +1,15 @@
 mod value;
 mod visitor;
 
-use {super::Deserializer, serde::Deserializer as _, std::io::Cursor};
+use {
+    super::{
+        super::{DeserializationError, LimitKind, Limits},
+        Deserializer,
+    },
+    crate::StringEncoding,
+    serde::Deserializer as _,
+    std::io::Cursor,
+};
 
 pub use self::{value::Value, visitor::Visitor};
 
@@ -224,6 +232,121 @@ fn deserialize_str_without_padding() {
     assert_eq!(result, Value::String("Hey!".to_string()));
 }
 
+#[test]
+fn deserialize_str_with_non_zero_padding() {
+    let mut cursor =
+        Cursor::new(vec![0x00, 0x00, 0x00, 0x02, b'H', b'i', 0x00, 0x01]);
+
+    let result = Deserializer::new(&mut cursor)
+        .deserialize_str(Visitor)
+        .unwrap();
+
+    assert_eq!(cursor.position(), 8);
+    assert_eq!(result, Value::String("Hi".to_string()));
+}
+
+#[test]
+fn deserialize_str_with_non_zero_padding_in_strict_mode() {
+    let mut cursor =
+        Cursor::new(vec![0x00, 0x00, 0x00, 0x02, b'H', b'i', 0x00, 0x01]);
+
+    let error = Deserializer::new(&mut cursor)
+        .with_strict_mode(true)
+        .deserialize_str(Visitor)
+        .unwrap_err();
+
+    match DeserializationError::from(error) {
+        DeserializationError::NonZeroPadding { type_name, padding } => {
+            assert_eq!(type_name, "string");
+            assert_eq!(padding, vec![0x00, 0x01]);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn deserialize_str_with_zero_padding_in_strict_mode() {
+    let mut cursor =
+        Cursor::new(vec![0x00, 0x00, 0x00, 0x03, b'H', b'i', b'!', 0x00]);
+
+    let result = Deserializer::new(&mut cursor)
+        .with_strict_mode(true)
+        .deserialize_str(Visitor)
+        .unwrap();
+
+    assert_eq!(cursor.position(), 8);
+    assert_eq!(result, Value::String("Hi!".to_string()));
+}
+
+#[test]
+fn deserialize_latin1_str() {
+    let mut cursor =
+        Cursor::new(vec![0x00, 0x00, 0x00, 0x03, b'c', 0xe9, 0xff, 0x00]);
+
+    let result = Deserializer::new(&mut cursor)
+        .with_string_encoding(StringEncoding::Latin1)
+        .deserialize_str(Visitor)
+        .unwrap();
+
+    assert_eq!(cursor.position(), 8);
+    assert_eq!(result, Value::String("c\u{e9}\u{ff}".to_string()));
+}
+
+#[test]
+fn deserialize_non_ascii_str_with_ascii_encoding() {
+    let mut cursor =
+        Cursor::new(vec![0x00, 0x00, 0x00, 0x02, 0xc3, 0xa9, 0x00, 0x00]);
+
+    let error = Deserializer::new(&mut cursor)
+        .with_string_encoding(StringEncoding::Ascii)
+        .deserialize_str(Visitor)
+        .unwrap_err();
+
+    match DeserializationError::from(error) {
+        DeserializationError::InvalidAsciiString { bytes } => {
+            assert_eq!(bytes, vec![0xc3, 0xa9]);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn deserialize_char_outside_of_latin1() {
+    let mut cursor = Cursor::new(vec![0x00, 0x00, 0x01, 0x00]);
+
+    let error = Deserializer::new(&mut cursor)
+        .with_string_encoding(StringEncoding::Latin1)
+        .deserialize_char(Visitor)
+        .unwrap_err();
+
+    match DeserializationError::from(error) {
+        DeserializationError::InvalidChar { raw_value } => {
+            assert_eq!(raw_value, 0x100);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn deserialize_opaque_with_non_zero_padding_in_strict_mode() {
+    let mut cursor = Cursor::new(vec![
+        0x00, 0x00, 0x00, 0x05, 0x01, 0x02, 0x03, 0x04, 0xff, 0x00, 0x80, 0x00,
+    ]);
+
+    let error = Deserializer::new(&mut cursor)
+        .with_strict_mode(true)
+        .deserialize_bytes(Visitor)
+        .unwrap_err();
+
+    match DeserializationError::from(error) {
+        DeserializationError::NonZeroPadding { type_name, padding } => {
+            assert_eq!(type_name, "opaque");
+            assert_eq!(padding, vec![0x00, 0x80, 0x00]);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
 #[test]
 fn deserialize_opaque_without_padding() {
     let mut cursor = Cursor::new(vec![
@@ -336,3 +459,104 @@ fn deserialize_newtype_struct() {
     assert_eq!(cursor.position(), 4);
     assert_eq!(result, Value::Integer32(-16));
 }
+
+#[test]
+fn length_prefix_exceeding_limit_is_rejected() {
+    let mut cursor = Cursor::new(vec![0xff, 0xff, 0xff, 0xfc]);
+    let limits = Limits::default().with_max_length(1024);
+
+    let error = Deserializer::new(&mut cursor)
+        .with_limits(limits)
+        .deserialize_bytes(Visitor)
+        .unwrap_err();
+
+    match DeserializationError::from(error) {
+        DeserializationError::LimitExceeded {
+            limit,
+            requested,
+            maximum,
+        } => {
+            assert_eq!(limit, LimitKind::Length);
+            assert_eq!(requested, 0xffff_fffc);
+            assert_eq!(maximum, 1024);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn unrepresentable_padded_length_is_rejected() {
+    let mut cursor = Cursor::new(vec![]);
+
+    let error = Deserializer::new(&mut cursor)
+        .deserialize_opaque_contents(usize::MAX - 1, "opaque")
+        .unwrap_err();
+
+    match DeserializationError::from(error) {
+        DeserializationError::LimitExceeded {
+            limit,
+            requested,
+            maximum,
+        } => {
+            assert_eq!(limit, LimitKind::Length);
+            assert_eq!(requested, (usize::MAX - 1) as u64);
+            assert_eq!(maximum, (usize::MAX - 3) as u64);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn total_bytes_exceeding_limit_are_rejected() {
+    let mut cursor =
+        Cursor::new(vec![0x00, 0x00, 0x00, 0x05, b'H', b'e', b'l', b'l']);
+    let limits = Limits::default().with_max_total_bytes(8);
+
+    let error = Deserializer::new(&mut cursor)
+        .with_limits(limits)
+        .deserialize_str(Visitor)
+        .unwrap_err();
+
+    match DeserializationError::from(error) {
+        DeserializationError::LimitExceeded {
+            limit,
+            requested,
+            maximum,
+        } => {
+            assert_eq!(limit, LimitKind::TotalBytes);
+            assert_eq!(requested, 12);
+            assert_eq!(maximum, 8);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+
+    assert_eq!(cursor.position(), 4);
+}
+
+#[test]
+fn cumulative_allocation_exceeding_limit_is_rejected() {
+    let mut cursor = Cursor::new(vec![
+        0x00, 0x00, 0x00, 0x03, b'a', b'b', b'c', 0x00, 0x00, 0x00, 0x00, 0x02,
+        b'd', b'e', 0x00, 0x00,
+    ]);
+    let limits = Limits::default().with_max_allocation(4);
+    let mut deserializer = Deserializer::new(&mut cursor).with_limits(limits);
+
+    let result = deserializer.deserialize_bytes(Visitor).unwrap();
+    let error = deserializer.deserialize_bytes(Visitor).unwrap_err();
+
+    assert_eq!(result, Value::Bytes(b"abc".to_vec()));
+
+    match DeserializationError::from(error) {
+        DeserializationError::LimitExceeded {
+            limit,
+            requested,
+            maximum,
+        } => {
+            assert_eq!(limit, LimitKind::Allocation);
+            assert_eq!(requested, 5);
+            assert_eq!(maximum, 4);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
@@ -1,4 +1,6 @@
 use {
+    super::LimitKind,
+    crate::{Path, PathSegment},
     failure::{Compat, Fail},
     serde::de,
     std::{
@@ -21,9 +23,20 @@ pub enum DeserializationError {
 
     /// Failure while deserializing a value.
     #[fail(display = "failed to deserialize a value of type: {}", type_name)]
+    #[non_exhaustive]
     Failure {
         /// The name of the type that was being deserialized.
         type_name: String,
+        /// The position of the value inside the value that contains it, if
+        /// it's part of a path.
+        segment: Option<PathSegment>,
+        /// The number of bytes the deserializer had read before the start of
+        /// the value, if known.
+        ///
+        /// The offset is relative to where the reader was when the
+        /// deserializer was created, so it's only an offset in the input if
+        /// the deserializer started reading at its beginning.
+        offset: Option<u64>,
         /// The error that ocurred during deserialization.
         #[cause]
         cause: Box<CompatDeserializationError>,
@@ -33,6 +46,13 @@ pub enum DeserializationError {
     #[fail(display = "deserialization of an identifier is not supported")]
     IdentifierNotSupported,
 
+    /// Deserialized a string that isn't ASCII when only ASCII is accepted.
+    #[fail(display = "deserialized a string that is not ASCII: {:?}", bytes)]
+    InvalidAsciiString {
+        /// The deserialized bytes of the string.
+        bytes: Vec<u8>,
+    },
+
     /// Deserialized boolean value is invalid.
     #[fail(display = "deserialized an invalid bool: {}", raw_value)]
     InvalidBool {
@@ -48,7 +68,6 @@ pub enum DeserializationError {
     },
 
     /// Deserialized enum variant is invalid.
-    #[cfg(not(feature = "ignore-enum-variant-names"))]
     #[fail(
         display = "deserialized an invalid enum variant: variant index is {}, valid variants are {:?}",
         variant, variants
@@ -96,6 +115,18 @@ pub enum DeserializationError {
         value: u32,
     },
 
+    /// Deserialized value is not one of the values accepted by its type.
+    #[fail(
+        display = "deserialized an invalid value: {}, expected {}",
+        value, expected
+    )]
+    InvalidValue {
+        /// Description of the deserialized value.
+        value: String,
+        /// Description of the values that were expected.
+        expected: String,
+    },
+
     /// IO error while deserializing a value.
     #[fail(
         display = "IO error while deserializing a value of type {}: {}",
@@ -109,10 +140,59 @@ pub enum DeserializationError {
         cause: io::Error,
     },
 
+    /// Deserialized length is larger than the maximum length of the type.
+    #[fail(
+        display = "deserialized length {} exceeds the maximum of {}",
+        length, maximum
+    )]
+    LengthExceedsMaximum {
+        /// The deserialized length.
+        length: u32,
+        /// The maximum length allowed for the type.
+        maximum: u32,
+    },
+
+    /// A configured resource limit was exceeded.
+    #[fail(
+        display = "{} limit exceeded: {} is larger than the maximum of {}",
+        limit, requested, maximum
+    )]
+    LimitExceeded {
+        /// The limit that was exceeded.
+        limit: LimitKind,
+        /// The amount that would be used if the input was accepted.
+        requested: u64,
+        /// The configured limit.
+        maximum: u64,
+    },
+
     /// Map types are not supported by XDR.
     #[fail(display = "XDR does not support a map type")]
     MapIsNotSupported,
 
+    /// Deserialized padding bytes that aren't zero in strict mode.
+    #[fail(
+        display = "deserialized non-zero padding in {}: {:?}",
+        type_name, padding
+    )]
+    NonZeroPadding {
+        /// The name of the type that was being deserialized.
+        type_name: String,
+        /// The padding bytes that were read.
+        padding: Vec<u8>,
+    },
+
+    /// Input remained after the deserialized value.
+    #[fail(
+        display = "{} trailing bytes after the deserialized value",
+        remaining
+    )]
+    TrailingBytes {
+        /// The number of bytes that were found after the value, which is only
+        /// a lower bound when deserializing from a reader.
+        remaining: u64,
+    },
+
     /// Attempt to deserialize a tuple that has too many elements.
     #[fail(display = "tuple has too many elements: {}", length)]
     TupleHasTooManyElements {
@@ -134,10 +214,72 @@ impl DeserializationError {
     {
         DeserializationError::Failure {
             type_name: type_name.to_string(),
+            segment: None,
+            offset: None,
             cause: Box::new(cause.into()),
         }
     }
 
+    /// Create a deserialization failure error of a value at a known location.
+    pub(crate) fn failure_at<S, E>(
+        type_name: S,
+        segment: PathSegment,
+        offset: u64,
+        cause: E,
+    ) -> Self
+    where
+        S: ToString,
+        E: Into<CompatDeserializationError>,
+    {
+        DeserializationError::Failure {
+            type_name: type_name.to_string(),
+            segment: Some(segment),
+            offset: Some(offset),
+            cause: Box::new(cause.into()),
+        }
+    }
+
+    /// Retrieve the path to the value that failed to be deserialized.
+    pub fn path(&self) -> Path {
+        let mut segments = Vec::new();
+        let mut error = self;
+
+        while let DeserializationError::Failure { segment, cause, .. } = error {
+            segments.extend(segment.clone());
+            error = cause.get_ref();
+        }
+
+        Path::new(segments)
+    }
+
+    /// Retrieve the offset in bytes of the innermost value that failed to be
+    /// deserialized, if known.
+    ///
+    /// The offset counts the bytes read since the deserializer was created.
+    pub fn offset(&self) -> Option<u64> {
+        let mut innermost_offset = None;
+        let mut error = self;
+
+        while let DeserializationError::Failure { offset, cause, .. } = error {
+            innermost_offset = offset.or(innermost_offset);
+            error = cause.get_ref();
+        }
+
+        innermost_offset
+    }
+
+    /// Retrieve the error that caused the failure, skipping the errors that
+    /// only describe what was being deserialized.
+    pub fn root_cause(&self) -> &DeserializationError {
+        let mut error = self;
+
+        while let DeserializationError::Failure { cause, .. } = error {
+            error = cause.get_ref();
+        }
+
+        error
+    }
+
     /// Create a deserialization IO error.
     pub fn io_error<S>(type_name: S, cause: io::Error) -> Self
     where
@@ -164,6 +306,13 @@ impl From<CompatDeserializationError> for DeserializationError {
 #[derive(Debug)]
 pub struct CompatDeserializationError(Compat<DeserializationError>);
 
+impl CompatDeserializationError {
+    /// Retrieve a reference to the wrapped error.
+    pub fn get_ref(&self) -> &DeserializationError {
+        self.0.get_ref()
+    }
+}
+
 impl From<DeserializationError> for CompatDeserializationError {
     fn from(error: DeserializationError) -> Self {
         CompatDeserializationError(error.compat())
@@ -172,7 +321,25 @@ impl From<DeserializationError> for CompatDeserializationError {
 
 impl Display for CompatDeserializationError {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        self.0.fmt(formatter)
+        let error = self.get_ref();
+
+        if let DeserializationError::Failure { .. } = error {
+            let path = error.path();
+
+            write!(formatter, "{}", error.root_cause())?;
+
+            if !path.is_empty() {
+                write!(formatter, " at {}", path)?;
+            }
+
+            if let Some(offset) = error.offset() {
+                write!(formatter, " (byte offset {})", offset)?;
+            }
+
+            Ok(())
+        } else {
+            self.0.fmt(formatter)
+        }
     }
 }
 
@@ -190,6 +357,18 @@ impl de::Error for CompatDeserializationError {
 
         CompatDeserializationError(error.compat())
     }
+
+    fn invalid_value(
+        unexpected: de::Unexpected,
+        expected: &dyn de::Expected,
+    ) -> Self {
+        let error = DeserializationError::InvalidValue {
+            value: unexpected.to_string(),
+            expected: expected.to_string(),
+        };
+
+        CompatDeserializationError(error.compat())
+    }
 }
 
 pub type Result<T> = result::Result<T, CompatDeserializationError>;
@@ -0,0 +1,114 @@
+use std::fmt::{self, Display, Formatter};
+
+/// Resource limits for deserializing untrusted input.
+///
+/// By default no limits are enforced, so the length prefixes in the input are
+/// trusted. Each limit can be configured separately, and deserialization
+/// fails with a `LimitExceeded` error before any resource is used beyond the
+/// configured limit.
+///
+/// ```
+/// # use serde_xdr::{Deserializer, Limits};
+/// # use std::io::Cursor;
+/// let limits = Limits::default()
+///     .with_max_length(1024)
+///     .with_max_total_bytes(64 * 1024)
+///     .with_max_allocation(16 * 1024)
+///     .with_max_depth(64);
+///
+/// let mut cursor = Cursor::new(vec![0x00, 0x00, 0x00, 0x01]);
+/// let deserializer = Deserializer::new(&mut cursor).with_limits(limits);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Limits {
+    max_length: Option<u32>,
+    max_total_bytes: Option<u64>,
+    max_allocation: Option<u64>,
+    max_depth: Option<u32>,
+}
+
+impl Limits {
+    /// Limit the length prefix of variable-length items.
+    ///
+    /// Applies to strings, opaque data, arrays and maps.
+    pub fn with_max_length(mut self, max_length: u32) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Limit the total number of bytes read from the input.
+    pub fn with_max_total_bytes(mut self, max_total_bytes: u64) -> Self {
+        self.max_total_bytes = Some(max_total_bytes);
+        self
+    }
+
+    /// Limit the cumulative number of bytes allocated for strings and opaque
+    /// data.
+    ///
+    /// The contents are accounted for even if they are borrowed from the
+    /// input. The limit also bounds the size hints given for arrays and maps.
+    pub fn with_max_allocation(mut self, max_allocation: u64) -> Self {
+        self.max_allocation = Some(max_allocation);
+        self
+    }
+
+    /// Limit how deeply values can be nested.
+    ///
+    /// Each option, enumeration, union, structure, array and map increases
+    /// the depth while its contents are deserialized, so limiting it prevents
+    /// deeply nested input, such as a long chain of optional data, from
+    /// overflowing the stack.
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Retrieve the maximum length of variable-length items, if limited.
+    pub fn max_length(&self) -> Option<u32> {
+        self.max_length
+    }
+
+    /// Retrieve the maximum number of bytes read from the input, if limited.
+    pub fn max_total_bytes(&self) -> Option<u64> {
+        self.max_total_bytes
+    }
+
+    /// Retrieve the maximum number of bytes allocated, if limited.
+    pub fn max_allocation(&self) -> Option<u64> {
+        self.max_allocation
+    }
+
+    /// Retrieve the maximum nesting depth, if limited.
+    pub fn max_depth(&self) -> Option<u32> {
+        self.max_depth
+    }
+}
+
+/// The kind of resource limit that was exceeded.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LimitKind {
+    /// The length prefix of a variable-length item.
+    Length,
+
+    /// The total number of bytes read from the input.
+    TotalBytes,
+
+    /// The cumulative number of bytes allocated.
+    Allocation,
+
+    /// The nesting depth of values.
+    Depth,
+}
+
+impl Display for LimitKind {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            LimitKind::Length => "length",
+            LimitKind::TotalBytes => "total bytes",
+            LimitKind::Allocation => "allocation",
+            LimitKind::Depth => "depth",
+        };
+
+        write!(formatter, "{}", name)
+    }
+}
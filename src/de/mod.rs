@@ -1,42 +1,240 @@
 use {
     self::{deserializer::SequenceDeserializer, errors::Result},
-    byteorder::{BigEndian, ReadBytesExt},
+    crate::{MapEncoding, StringEncoding},
     serde::{de::Visitor, Deserialize},
-    std::io::{Cursor, Read},
+    std::{
+        borrow::Cow,
+        io::{self, Cursor, Read},
+    },
 };
 
-pub use self::errors::{CompatDeserializationError, DeserializationError};
+pub use self::{
+    errors::{CompatDeserializationError, DeserializationError},
+    limits::{LimitKind, Limits},
+    read::{SliceReader, XdrRead},
+};
 
 /// Deserializer for the XDR format.
 ///
 /// Structure that holds a mutable borrow of the reader it deserializes data
 /// from. It has an implementation of `serde::Deserializer` so that it can
 /// deserialize data from its XDR representation.
+///
+/// Any type that implements `Read` can be used as the reader, in which case
+/// the deserialized data owns everything it deserialized. Using a
+/// [`SliceReader`](struct.SliceReader.html) instead allows borrowing strings
+/// and opaque data from the input.
 pub struct Deserializer<'r, R>
 where
-    R: ReadBytesExt + 'r,
+    R: ?Sized + 'r,
 {
     reader: &'r mut R,
+    length_bound: Option<u32>,
+    strict: bool,
+    map_encoding: MapEncoding,
+    string_encoding: Option<StringEncoding>,
+    ignore_enum_variant_names: bool,
+    limits: Limits,
+    bytes_read: u64,
+    allocated: u64,
+    depth: u32,
 }
 
 impl<'r, R> Deserializer<'r, R>
 where
-    R: ReadBytesExt + 'r,
+    R: ?Sized + 'r,
 {
     /// Create a new instance that deserializes data from the given generic
     /// reader.
     pub fn new(reader: &'r mut R) -> Self {
-        Deserializer { reader }
+        Deserializer {
+            reader,
+            length_bound: None,
+            strict: false,
+            map_encoding: MapEncoding::Disabled,
+            string_encoding: None,
+            ignore_enum_variant_names: false,
+            limits: Limits::default(),
+            bytes_read: 0,
+            allocated: 0,
+            depth: 0,
+        }
     }
 
-    fn deserialize_integer(&mut self, bits: u8) -> Result<i32> {
-        let value = self.reader.read_i32::<BigEndian>().map_err(|error| {
-            DeserializationError::io_error(
-                format!("signed {}-bit integer", bits),
-                error,
-            )
+    /// Enable or disable strict mode.
+    ///
+    /// In strict mode, only the canonical encoding of a value is accepted, so
+    /// deserialization fails if the padding bytes of opaque data or strings
+    /// aren't zero. Strict mode is disabled by default.
+    pub fn with_strict_mode(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Configure how maps are deserialized.
+    ///
+    /// Maps aren't supported by XDR, so by default deserializing a map fails.
+    /// See [`MapEncoding`](enum.MapEncoding.html) for the available encodings.
+    /// The order of the entries isn't checked.
+    pub fn with_map_encoding(mut self, map_encoding: MapEncoding) -> Self {
+        self.map_encoding = map_encoding;
+        self
+    }
+
+    /// Configure how strings and characters are deserialized.
+    ///
+    /// If no encoding is configured, any UTF-8 string is accepted. See
+    /// [`StringEncoding`](enum.StringEncoding.html) for the available
+    /// encodings.
+    pub fn with_string_encoding(mut self, encoding: StringEncoding) -> Self {
+        self.string_encoding = Some(encoding);
+        self
+    }
+
+    /// Enable or disable ignoring the names of enum variants.
+    ///
+    /// Normally, the deserialized variant index must refer to one of the
+    /// variant names given to `deserialize_enum`, and the name of the variant
+    /// is reported in errors. When the names are ignored, any variant index is
+    /// accepted and passed on to the visitor, which is useful for
+    /// implementations of `Deserialize` that don't provide the variant names.
+    /// The names are checked by default.
+    pub fn with_ignored_enum_variant_names(mut self, enabled: bool) -> Self {
+        self.ignore_enum_variant_names = enabled;
+        self
+    }
+
+    /// Configure the resource limits to enforce while deserializing.
+    ///
+    /// No limits are enforced by default. See [`Limits`](struct.Limits.html)
+    /// for the available limits.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    fn ensure_within_limit(
+        limit: LimitKind,
+        requested: u64,
+        maximum: Option<u64>,
+    ) -> Result<()> {
+        if let Some(maximum) = maximum {
+            ensure!(
+                requested <= maximum,
+                DeserializationError::LimitExceeded {
+                    limit,
+                    requested,
+                    maximum,
+                }
+            );
+        }
+
+        Ok(())
+    }
+
+    fn consume(&mut self, count: usize) -> Result<()> {
+        let total = self.bytes_read.saturating_add(count as u64);
+
+        Self::ensure_within_limit(
+            LimitKind::TotalBytes,
+            total,
+            self.limits.max_total_bytes(),
+        )?;
+
+        self.bytes_read = total;
+
+        Ok(())
+    }
+
+    fn allocate(&mut self, size: usize) -> Result<()> {
+        let total = self.allocated.saturating_add(size as u64);
+
+        Self::ensure_within_limit(
+            LimitKind::Allocation,
+            total,
+            self.limits.max_allocation(),
+        )?;
+
+        self.allocated = total;
+
+        Ok(())
+    }
+
+    fn nested<T, F>(&mut self, deserialize: F) -> Result<T>
+    where
+        F: FnOnce(&mut Self) -> Result<T>,
+    {
+        let depth = self.depth + 1;
+
+        Self::ensure_within_limit(
+            LimitKind::Depth,
+            depth.into(),
+            self.limits.max_depth().map(u64::from),
+        )?;
+
+        self.depth = depth;
+
+        let result = deserialize(self);
+
+        self.depth -= 1;
+
+        result
+    }
+
+    fn size_hint(&self, remaining: u32) -> usize {
+        let budget = self
+            .limits
+            .max_allocation()
+            .map(|maximum| maximum.saturating_sub(self.allocated))
+            .unwrap_or(u64::MAX);
+
+        (remaining as u64).min(budget) as usize
+    }
+}
+
+impl<'de, 'r, R> Deserializer<'r, R>
+where
+    R: XdrRead<'de> + ?Sized + 'r,
+{
+    /// Check that the whole input has been consumed.
+    ///
+    /// Should be called after the value has been deserialized, in order to
+    /// reject input that has more data after it. At most one more byte is
+    /// read from a reader, so this doesn't block waiting for the rest of a
+    /// stream. Deserialization fails with a `TrailingBytes` error if any input
+    /// is left, reporting how many bytes were found.
+    pub fn end(&mut self) -> Result<()> {
+        let remaining = self.reader.trailing_bytes().map_err(|error| {
+            DeserializationError::io_error("end of input", error)
         })?;
 
+        ensure!(
+            remaining == 0,
+            DeserializationError::TrailingBytes { remaining }
+        );
+
+        Ok(())
+    }
+
+    fn read_array<const N: usize, S>(&mut self, type_name: S) -> Result<[u8; N]>
+    where
+        S: ToString,
+    {
+        let mut bytes = [0; N];
+
+        self.consume(N)?;
+        self.reader.read_into(&mut bytes).map_err(|error| {
+            DeserializationError::io_error(type_name, error)
+        })?;
+
+        Ok(bytes)
+    }
+
+    fn deserialize_integer(&mut self, bits: u8) -> Result<i32> {
+        let value = self
+            .read_array(format_args!("signed {}-bit integer", bits))
+            .map(i32::from_be_bytes)?;
+
         let most_significant_bit: u32 = 1 << (bits - 1);
         let max_value = (most_significant_bit - 1) as i32;
         let min_value = -max_value - 1;
@@ -49,12 +247,9 @@ where
     }
 
     fn deserialize_unsigned_integer(&mut self, bits: u8) -> Result<u32> {
-        let value = self.reader.read_u32::<BigEndian>().map_err(|error| {
-            DeserializationError::io_error(
-                format!("unsigned {}-bit integer", bits),
-                error,
-            )
-        })?;
+        let value = self
+            .read_array(format_args!("unsigned {}-bit integer", bits))
+            .map(u32::from_be_bytes)?;
 
         let most_significant_bit: u64 = 1 << bits;
         let max_value = (most_significant_bit - 1) as u32;
@@ -67,7 +262,7 @@ where
         Ok(value)
     }
 
-    fn deserialize_sequence<'de, V, S>(
+    fn deserialize_sequence<V, S>(
         &mut self,
         visitor: V,
         type_name: S,
@@ -78,28 +273,97 @@ where
         S: AsRef<str>,
     {
         let type_name = type_name.as_ref();
-        let deserializer = SequenceDeserializer::new(length, &type_name, self);
 
-        Ok(visitor.visit_seq(deserializer)?)
+        self.nested(|deserializer| {
+            visitor.visit_seq(SequenceDeserializer::new(
+                length,
+                &type_name,
+                deserializer,
+            ))
+        })
     }
 
-    fn deserialize_opaque(&mut self, type_name: &str) -> Result<Vec<u8>> {
-        let length = self.reader.read_u32::<BigEndian>().map_err(|error| {
-            DeserializationError::io_error(type_name, error)
-        })?;
+    fn deserialize_length(&mut self, type_name: &str) -> Result<u32> {
+        let length = self.read_array(type_name).map(u32::from_be_bytes)?;
+
+        Self::ensure_within_limit(
+            LimitKind::Length,
+            length.into(),
+            self.limits.max_length().map(u64::from),
+        )?;
 
-        let padding_size = 4 - (length + 3) % 4 - 1;
-        let buffer_length = length + padding_size;
+        if let Some(maximum) = self.length_bound.take() {
+            ensure!(
+                length <= maximum,
+                DeserializationError::LengthExceedsMaximum { length, maximum }
+            );
+        }
 
-        let mut buffer = Vec::with_capacity(buffer_length as usize);
+        Ok(length)
+    }
 
-        buffer.resize(buffer_length as usize, 0);
-        self.reader.read_exact(&mut buffer).map_err(|error| {
-            DeserializationError::io_error(type_name, error)
-        })?;
-        buffer.truncate(length as usize);
+    fn deserialize_opaque(
+        &mut self,
+        type_name: &str,
+    ) -> Result<Cow<'de, [u8]>> {
+        let length = self.deserialize_length(type_name)?;
+
+        self.deserialize_opaque_contents(length as usize, type_name)
+    }
 
-        Ok(buffer)
+    fn deserialize_opaque_contents(
+        &mut self,
+        length: usize,
+        type_name: &str,
+    ) -> Result<Cow<'de, [u8]>> {
+        let padding_size = (4 - length % 4) % 4;
+
+        // The padded length can only overflow where `usize` is 32 bits wide,
+        // in which case the largest multiple of four is the maximum length
+        let buffer_length = length.checked_add(padding_size).ok_or(
+            DeserializationError::LimitExceeded {
+                limit: LimitKind::Length,
+                requested: length as u64,
+                maximum: (usize::MAX - 3) as u64,
+            },
+        )?;
+
+        self.consume(buffer_length)?;
+        self.allocate(length)?;
+
+        let buffer = self
+            .reader
+            .read_bytes(buffer_length)
+            .and_then(|buffer| {
+                if buffer.len() == buffer_length {
+                    Ok(buffer)
+                } else {
+                    Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "reader returned fewer bytes than requested",
+                    ))
+                }
+            })
+            .map_err(|error| {
+                DeserializationError::io_error(type_name, error)
+            })?;
+
+        let padding = &buffer[length..];
+
+        if self.strict && padding.iter().any(|&byte| byte != 0) {
+            bail!(DeserializationError::NonZeroPadding {
+                type_name: type_name.to_string(),
+                padding: padding.to_vec(),
+            });
+        }
+
+        Ok(match buffer {
+            Cow::Borrowed(bytes) => Cow::Borrowed(&bytes[..length]),
+            Cow::Owned(mut bytes) => {
+                bytes.truncate(length);
+                Cow::Owned(bytes)
+            }
+        })
     }
 }
 
@@ -109,8 +373,9 @@ where
 /// implements `Read`.
 ///
 /// The lifetimes of the deserialized data `'de` and of the reader `'r` are
-/// different because the deserializer currently is not zero-copy, which means
-/// the returned data owns everything it deserialized.
+/// different because the data is copied out of the reader, which means the
+/// returned data owns everything it deserialized. Use
+/// [`from_slice`](fn.from_slice.html) to borrow data from the input instead.
 pub fn from_reader<'de, 'r, R, T>(reader: &'r mut R) -> Result<T>
 where
     R: Read,
@@ -126,8 +391,9 @@ where
 /// Deserializes data of a given type `T` from a generic instance that can be
 /// accessed as a reference to a slice of bytes.
 ///
-/// The deserializer is currently zero-copy, which means that the returned data
-/// owns everything it deserialized.
+/// The returned data owns everything it deserialized, since the bytes may not
+/// outlive the call. Use [`from_slice`](fn.from_slice.html) to borrow data from
+/// the input instead.
 pub fn from_bytes<'de, B, T>(bytes: B) -> Result<T>
 where
     B: AsRef<[u8]>,
@@ -138,5 +404,76 @@ where
     from_reader(&mut reader)
 }
 
+/// Deserializes data from a slice of bytes, rejecting non-canonical encodings.
+///
+/// Works like [`from_bytes`](fn.from_bytes.html), but deserializes in strict
+/// mode, so that data that isn't in its canonical form, such as opaque data or
+/// strings with padding bytes that aren't zero, is rejected.
+pub fn from_bytes_strict<'de, B, T>(bytes: B) -> Result<T>
+where
+    B: AsRef<[u8]>,
+    T: Deserialize<'de>,
+{
+    let mut reader = Cursor::new(bytes);
+    let mut deserializer =
+        Deserializer::new(&mut reader).with_strict_mode(true);
+
+    T::deserialize(&mut deserializer)
+}
+
+/// Deserializes data from a slice of bytes, requiring all bytes to be used.
+///
+/// Works like [`from_bytes`](fn.from_bytes.html), but fails with a
+/// `TrailingBytes` error if there are bytes left after the deserialized value.
+pub fn from_bytes_exact<'de, B, T>(bytes: B) -> Result<T>
+where
+    B: AsRef<[u8]>,
+    T: Deserialize<'de>,
+{
+    let mut reader = Cursor::new(bytes);
+    let value = T::deserialize(&mut Deserializer::new(&mut reader))?;
+    let length = reader.get_ref().as_ref().len() as u64;
+    let remaining = length.saturating_sub(reader.position());
+
+    ensure!(
+        remaining == 0,
+        DeserializationError::TrailingBytes { remaining }
+    );
+
+    Ok(value)
+}
+
+/// Deserializes data from a slice of bytes, borrowing from it when possible.
+///
+/// Deserializes data of a given type `T` that may borrow from the slice, so
+/// that types like `&'de str`, `&'de [u8]` and `Cow<'de, str>` refer to the
+/// input without copying it.
+pub fn from_slice<'de, T>(bytes: &'de [u8]) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    let mut reader = SliceReader::new(bytes);
+    let mut deserializer = Deserializer::new(&mut reader);
+
+    T::deserialize(&mut deserializer)
+}
+
+/// Deserializes data from the start of a slice of bytes.
+///
+/// Deserializes data of a given type `T` that may borrow from the slice, like
+/// [`from_slice`](fn.from_slice.html), and returns it together with the bytes
+/// that come after it.
+pub fn take_from_bytes<'de, T>(bytes: &'de [u8]) -> Result<(T, &'de [u8])>
+where
+    T: Deserialize<'de>,
+{
+    let mut reader = SliceReader::new(bytes);
+    let value = T::deserialize(&mut Deserializer::new(&mut reader))?;
+
+    Ok((value, reader.remaining()))
+}
+
 mod deserializer;
 mod errors;
+mod limits;
+mod read;
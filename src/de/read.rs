@@ -0,0 +1,127 @@
+use std::{
+    borrow::Cow,
+    io::{self, Read},
+};
+
+/// Source of the bytes that are deserialized.
+///
+/// Implemented for every type that implements `Read`, in which case the
+/// deserialized data is always copied, and for [`SliceReader`], which
+/// allows strings and opaque data to be borrowed from the input.
+///
+/// [`SliceReader`]: struct.SliceReader.html
+pub trait XdrRead<'de> {
+    /// Read the exact number of bytes required to fill the buffer.
+    fn read_into(&mut self, buffer: &mut [u8]) -> io::Result<()>;
+
+    /// Read a given number of bytes, borrowing them from the input if
+    /// possible.
+    fn read_bytes(&mut self, length: usize) -> io::Result<Cow<'de, [u8]>>;
+
+    /// Check if there is input left, without consuming it from the
+    /// deserialized data.
+    ///
+    /// Returns zero at the end of the input, and otherwise the number of bytes
+    /// that were found. Readers only look for a single byte, so that checking
+    /// the end of a stream never waits for or reads the rest of it.
+    fn trailing_bytes(&mut self) -> io::Result<u64>;
+}
+
+impl<'de, R> XdrRead<'de> for R
+where
+    R: Read + ?Sized,
+{
+    fn read_into(&mut self, buffer: &mut [u8]) -> io::Result<()> {
+        self.read_exact(buffer)
+    }
+
+    fn read_bytes(&mut self, length: usize) -> io::Result<Cow<'de, [u8]>> {
+        // The length usually comes from the input, so the buffer only grows
+        // as the bytes are actually read, instead of being allocated upfront
+        let mut buffer = Vec::new();
+
+        Read::take(&mut *self, length as u64).read_to_end(&mut buffer)?;
+
+        if buffer.len() < length {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "failed to fill whole buffer",
+            ));
+        }
+
+        Ok(Cow::Owned(buffer))
+    }
+
+    fn trailing_bytes(&mut self) -> io::Result<u64> {
+        let mut byte = [0];
+
+        loop {
+            match self.read(&mut byte) {
+                Ok(count) => return Ok(count as u64),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+/// Reader of a slice of bytes that lends out parts of the slice.
+///
+/// Deserializing from a `SliceReader` allows `&'de str`, `&'de [u8]` and
+/// other borrowed types to refer directly to the input, without copying the
+/// deserialized strings and opaque data.
+#[derive(Clone, Debug)]
+pub struct SliceReader<'de> {
+    bytes: &'de [u8],
+    position: usize,
+}
+
+impl<'de> SliceReader<'de> {
+    /// Create a reader of the given slice of bytes.
+    pub fn new(bytes: &'de [u8]) -> Self {
+        SliceReader { bytes, position: 0 }
+    }
+
+    /// Retrieve the number of bytes that have been read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Retrieve the bytes that haven't been read yet.
+    pub fn remaining(&self) -> &'de [u8] {
+        &self.bytes[self.position..]
+    }
+
+    fn take(&mut self, length: usize) -> io::Result<&'de [u8]> {
+        let remaining = self.remaining();
+
+        if length > remaining.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "failed to fill whole buffer",
+            ));
+        }
+
+        self.position += length;
+
+        Ok(&remaining[..length])
+    }
+}
+
+impl<'de> XdrRead<'de> for SliceReader<'de> {
+    fn read_into(&mut self, buffer: &mut [u8]) -> io::Result<()> {
+        let bytes = self.take(buffer.len())?;
+
+        buffer.copy_from_slice(bytes);
+
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, length: usize) -> io::Result<Cow<'de, [u8]>> {
+        self.take(length).map(Cow::Borrowed)
+    }
+
+    fn trailing_bytes(&mut self) -> io::Result<u64> {
+        Ok(self.remaining().len() as u64)
+    }
+}
@@ -0,0 +1,100 @@
+use {
+    serde::{
+        de::{Deserialize, Deserializer, Error, Unexpected},
+        ser::{Serialize, Serializer},
+    },
+    std::{any::type_name, convert::TryFrom},
+};
+
+/// Serialize a fieldless enum as the signed integer value of its variant.
+///
+/// An XDR `enum` is encoded as the signed integer value of the enumerator,
+/// which can be negative and doesn't have to match the index of the variant.
+/// This function and [`deserialize`](fn.deserialize.html) encode a fieldless
+/// Rust enum using its declared discriminant. The enum must be convertible
+/// into an `i32`, and fallibly convertible from an `i32`, which can be
+/// implemented by hand or derived with crates like `num_enum`.
+///
+/// # Examples
+///
+/// ```
+/// extern crate serde_xdr;
+/// #[macro_use]
+/// extern crate serde_derive;
+///
+/// use std::convert::TryFrom;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// #[repr(i32)]
+/// enum Color {
+///     Red = 2,
+///     Green = 3,
+///     Blue = -1,
+/// }
+///
+/// impl From<Color> for i32 {
+///     fn from(color: Color) -> i32 {
+///         color as i32
+///     }
+/// }
+///
+/// impl TryFrom<i32> for Color {
+///     type Error = i32;
+///
+///     fn try_from(value: i32) -> Result<Self, i32> {
+///         match value {
+///             2 => Ok(Color::Red),
+///             3 => Ok(Color::Green),
+///             -1 => Ok(Color::Blue),
+///             other => Err(other),
+///         }
+///     }
+/// }
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// struct Pixel {
+///     #[serde(with = "serde_xdr::enumeration")]
+///     color: Color,
+/// }
+///
+/// fn main() {
+///     let pixel = Pixel { color: Color::Blue };
+///
+///     let bytes = serde_xdr::to_bytes(&pixel).unwrap();
+///
+///     assert_eq!(bytes, vec![0xff, 0xff, 0xff, 0xff]);
+///
+///     let deserialized_pixel: Pixel = serde_xdr::from_bytes(&bytes).unwrap();
+///
+///     assert_eq!(deserialized_pixel, pixel);
+/// }
+/// ```
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Copy + Into<i32>,
+    S: Serializer,
+{
+    let value: i32 = (*value).into();
+
+    value.serialize(serializer)
+}
+
+/// Deserialize a signed integer into the fieldless enum variant that has that
+/// value.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<i32>,
+{
+    let value = i32::deserialize(deserializer)?;
+
+    T::try_from(value).map_err(|_| {
+        D::Error::invalid_value(
+            Unexpected::Signed(value.into()),
+            &&*format!("a value of enum {}", type_name::<T>()),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests;
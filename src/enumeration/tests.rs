@@ -0,0 +1,88 @@
+use {
+    super::super::{from_bytes, to_bytes, DeserializationError},
+    std::convert::TryFrom,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(i32)]
+enum Color {
+    Red = 2,
+    Green = 3,
+    Blue = -1,
+}
+
+impl From<Color> for i32 {
+    fn from(color: Color) -> i32 {
+        color as i32
+    }
+}
+
+impl TryFrom<i32> for Color {
+    type Error = i32;
+
+    fn try_from(value: i32) -> Result<Self, i32> {
+        match value {
+            2 => Ok(Color::Red),
+            3 => Ok(Color::Green),
+            -1 => Ok(Color::Blue),
+            other => Err(other),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Pixel {
+    #[serde(with = "super")]
+    color: Color,
+}
+
+#[test]
+fn serialize_enumeration() {
+    let pixels = vec![
+        Pixel { color: Color::Red },
+        Pixel {
+            color: Color::Green,
+        },
+        Pixel { color: Color::Blue },
+    ];
+
+    let expected_bytes = vec![
+        0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03,
+        0xff, 0xff, 0xff, 0xff,
+    ];
+
+    assert_eq!(to_bytes(&pixels).unwrap(), expected_bytes);
+}
+
+#[test]
+fn deserialize_enumeration() {
+    let bytes = [
+        0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03,
+        0xff, 0xff, 0xff, 0xff,
+    ];
+
+    let pixels: Vec<Pixel> = from_bytes(bytes).unwrap();
+
+    let expected_pixels = vec![
+        Pixel { color: Color::Red },
+        Pixel {
+            color: Color::Green,
+        },
+        Pixel { color: Color::Blue },
+    ];
+
+    assert_eq!(pixels, expected_pixels);
+}
+
+#[test]
+fn invalid_enumeration_value_is_reported() {
+    let error = from_bytes::<_, Pixel>([0x00, 0x00, 0x00, 0x00]).unwrap_err();
+
+    match DeserializationError::from(error).root_cause() {
+        DeserializationError::InvalidValue { value, expected } => {
+            assert_eq!(value, "integer `0`");
+            assert!(expected.contains("Color"), "{}", expected);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
@@ -9,6 +9,7 @@
 //! - [`serde_xdr::ser::to_bytes`](fn.to_bytes.html)
 //! - [`serde_xdr::ser::to_writer`](fn.to_writer.html)
 //! - [`serde_xdr::de::from_reader`](fn.from_reader.html)
+//! - [`serde_xdr::de::from_slice`](fn.from_slice.html)
 //!
 //! [1]: https://tools.ietf.org/html/rfc1014
 //! [2]: https://serde.rs
@@ -86,19 +87,40 @@ extern crate serde_bytes;
 mod errors;
 
 mod de;
+mod map_encoding;
+mod options;
+mod path;
 mod ser;
+mod string_encoding;
 
 #[cfg(test)]
 mod tests;
 
+/// Serialization and deserialization functions for arrays.
+pub mod array;
+/// Variable-length arrays, opaque data and strings with a maximum length.
+pub mod bounded;
+/// Serialization and deserialization functions for XDR enumerations.
+pub mod enumeration;
+/// Serialization and deserialization functions for XDR linked lists.
+pub mod linked_list;
 /// Serialization and deserialization functions for opaque data.
 pub mod opaque_data;
+/// Quadruple-precision floating-point numbers.
+pub mod quadruple;
+/// Discriminated unions with explicit discriminant values.
+pub mod union;
 
 pub use de::{
-    from_bytes, from_reader, CompatDeserializationError, DeserializationError,
-    Deserializer,
+    from_bytes, from_bytes_exact, from_bytes_strict, from_reader, from_slice,
+    take_from_bytes, CompatDeserializationError, DeserializationError,
+    Deserializer, LimitKind, Limits, SliceReader, XdrRead,
 };
+pub use map_encoding::MapEncoding;
+pub use options::Options;
+pub use path::{Path, PathSegment};
 pub use ser::{
-    to_bytes, to_writer, CompatSerializationError, SerializationError,
-    Serializer,
+    serialized_size, to_bytes, to_slice, to_writer, CompatSerializationError,
+    SerializationError, Serializer,
 };
+pub use string_encoding::StringEncoding;
@@ -0,0 +1,143 @@
+use {
+    serde::{
+        de::{Deserialize, Deserializer, Error as _, SeqAccess, Visitor},
+        ser::{Error as _, Serialize, SerializeTuple, Serializer},
+    },
+    std::{
+        fmt::{self, Formatter},
+        marker::PhantomData,
+    },
+};
+
+/// Serialize a list of entries as an XDR linked list.
+///
+/// XDR has no list type, so lists are usually declared as a chain of
+/// optional-data pointers to the next entry:
+///
+/// ```text
+/// struct entry {
+///     int value;
+///     entry *next;
+/// };
+/// ```
+///
+/// On the wire, each entry is preceded by a boolean one, and the list ends
+/// with a boolean zero. This function and [`deserialize`](fn.deserialize.html)
+/// map a `Vec<T>` to and from that form, where `T` is the entry without its
+/// `next` pointer. Both directions handle one entry at a time, so long lists
+/// don't need recursive types and don't use more stack space than short ones.
+///
+/// # Examples
+///
+/// ```
+/// extern crate serde_xdr;
+/// #[macro_use]
+/// extern crate serde_derive;
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// struct Directory {
+///     #[serde(with = "serde_xdr::linked_list")]
+///     entries: Vec<u32>,
+/// }
+///
+/// fn main() {
+///     let directory = Directory {
+///         entries: vec![7, 9],
+///     };
+///
+///     let bytes = serde_xdr::to_bytes(&directory).unwrap();
+///
+///     assert_eq!(
+///         bytes,
+///         vec![0, 0, 0, 1, 0, 0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 9, 0, 0, 0, 0]
+///     );
+///
+///     let deserialized_directory: Directory =
+///         serde_xdr::from_bytes(&bytes).unwrap();
+///
+///     assert_eq!(deserialized_directory, directory);
+/// }
+/// ```
+pub fn serialize<T, S>(list: &[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    let length = list
+        .len()
+        .checked_mul(2)
+        .and_then(|length| length.checked_add(1))
+        .ok_or_else(|| S::Error::custom("linked list is too long"))?;
+
+    let mut tuple_serializer = serializer.serialize_tuple(length)?;
+
+    for entry in list {
+        tuple_serializer.serialize_element(&true)?;
+        tuple_serializer.serialize_element(entry)?;
+    }
+
+    tuple_serializer.serialize_element(&false)?;
+    tuple_serializer.end()
+}
+
+/// Deserialize an XDR linked list into a list of its entries.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    // The number of entries is only known once the list ends, so the tuple is
+    // declared with the largest length XDR supports, and the visitor stops
+    // reading when it finds the end of the list
+    deserializer.deserialize_tuple(u32::MAX as usize, LinkedListVisitor::new())
+}
+
+struct LinkedListVisitor<T> {
+    _entry: PhantomData<T>,
+}
+
+impl<T> LinkedListVisitor<T> {
+    pub fn new() -> Self {
+        LinkedListVisitor {
+            _entry: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> Visitor<'de> for LinkedListVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = Vec<T>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "a linked list")
+    }
+
+    fn visit_seq<A>(self, mut sequence: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut entries = Vec::new();
+
+        loop {
+            match sequence.next_element()? {
+                Some(true) => {}
+                Some(false) => return Ok(entries),
+                None => {
+                    return Err(A::Error::invalid_length(entries.len(), &self))
+                }
+            }
+
+            match sequence.next_element()? {
+                Some(entry) => entries.push(entry),
+                None => {
+                    return Err(A::Error::invalid_length(entries.len(), &self))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
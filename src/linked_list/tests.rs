@@ -0,0 +1,138 @@
+use {
+    super::super::{from_bytes, to_bytes, DeserializationError},
+    serde::de::value::{self, SeqDeserializer},
+};
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Entry {
+    file_id: u32,
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Directory {
+    #[serde(with = "super")]
+    entries: Vec<Entry>,
+    end_of_file: bool,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Numbers {
+    #[serde(with = "super")]
+    values: Vec<u32>,
+}
+
+#[test]
+fn serialize_empty_list() {
+    let numbers = Numbers { values: Vec::new() };
+
+    let bytes = to_bytes(&numbers).unwrap();
+
+    assert_eq!(bytes, vec![0, 0, 0, 0]);
+}
+
+#[test]
+fn serialize_list() {
+    let directory = Directory {
+        entries: vec![
+            Entry {
+                file_id: 1,
+                name: "a".to_string(),
+            },
+            Entry {
+                file_id: 2,
+                name: "bc".to_string(),
+            },
+        ],
+        end_of_file: true,
+    };
+
+    let bytes = to_bytes(&directory).unwrap();
+
+    let expected_bytes = vec![
+        0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, b'a', 0, 0, 0, 0, 0, 0, 1, 0, 0, 0,
+        2, 0, 0, 0, 2, b'b', b'c', 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+    ];
+
+    assert_eq!(bytes, expected_bytes);
+}
+
+#[test]
+fn deserialize_list() {
+    let bytes = vec![
+        0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, b'a', 0, 0, 0, 0, 0, 0, 1, 0, 0, 0,
+        2, 0, 0, 0, 2, b'b', b'c', 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+    ];
+
+    let directory: Directory = from_bytes(&bytes).unwrap();
+
+    let expected_directory = Directory {
+        entries: vec![
+            Entry {
+                file_id: 1,
+                name: "a".to_string(),
+            },
+            Entry {
+                file_id: 2,
+                name: "bc".to_string(),
+            },
+        ],
+        end_of_file: true,
+    };
+
+    assert_eq!(directory, expected_directory);
+}
+
+#[test]
+fn long_list_round_trip() {
+    let numbers = Numbers {
+        values: (0..100_000).collect(),
+    };
+
+    let bytes = to_bytes(&numbers).unwrap();
+    let deserialized_numbers: Numbers = from_bytes(&bytes).unwrap();
+
+    assert_eq!(bytes.len(), 100_000 * 8 + 4);
+    assert_eq!(deserialized_numbers, numbers);
+}
+
+#[test]
+fn invalid_marker_is_rejected() {
+    let bytes = vec![0, 0, 0, 1, 0, 0, 0, 7, 0, 0, 0, 2];
+
+    let error = from_bytes::<_, Numbers>(&bytes).unwrap_err();
+
+    match DeserializationError::from(error).root_cause() {
+        DeserializationError::InvalidBool { raw_value } => {
+            assert_eq!(*raw_value, 2)
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn truncated_list_is_rejected() {
+    let bytes = vec![0, 0, 0, 1, 0, 0, 0, 7];
+
+    let error = from_bytes::<_, Numbers>(&bytes).unwrap_err();
+
+    match DeserializationError::from(error).root_cause() {
+        DeserializationError::IoError { .. } => {}
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn unterminated_list_reports_number_of_entries() {
+    let markers_and_entries = vec![true, true, true, false, true];
+    let deserializer = SeqDeserializer::<_, value::Error>::new(
+        markers_and_entries.into_iter(),
+    );
+
+    let error = super::deserialize::<_, bool>(deserializer).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "invalid length 2, expected a linked list"
+    );
+}
@@ -0,0 +1,23 @@
+/// How maps are encoded.
+///
+/// XDR has no map type, so maps are rejected by default. When enabled, a map is
+/// encoded like the common hand-written XDR definition of a variable-length
+/// array of key and value pairs, `struct { K key; V value; } entries<>`: an
+/// unsigned 32-bit integer with the number of entries followed by each key and
+/// its value.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum MapEncoding {
+    /// Maps can't be serialized or deserialized.
+    #[default]
+    Disabled,
+
+    /// Entries are serialized in the order the map provides them.
+    Unsorted,
+
+    /// Entries are serialized sorted by the serialized bytes of their keys, so
+    /// that the output is deterministic even for maps without a defined
+    /// order, like `HashMap`.
+    ///
+    /// Deserialization accepts entries in any order.
+    Sorted,
+}
@@ -37,6 +37,9 @@
 pub mod fixed_length;
 
 mod byte_array;
+mod bytes;
+
+pub(crate) use self::bytes::{ByteBuf, Bytes};
 
 #[cfg(test)]
 mod tests;
@@ -1,6 +1,6 @@
 use {
     super::super::{
-        super::{from_reader, to_bytes},
+        super::{from_bytes, from_bytes_strict, from_reader, to_bytes},
         fixed_length,
     },
     std::io::Cursor,
@@ -109,3 +109,125 @@ fn deserialize_bytes_with_1_byte_padding() {
 
     assert_eq!(bytes.data, [0, 1, 2, 4, 8, 16, 32]);
 }
+
+#[derive(Deserialize, Serialize)]
+struct NoBytes {
+    #[serde(with = "fixed_length")]
+    data: [u8; 0],
+}
+
+#[derive(Deserialize, Serialize)]
+struct SixtyFiveBytes {
+    #[serde(with = "fixed_length")]
+    data: [u8; 65],
+}
+
+#[derive(Deserialize, Serialize)]
+struct Page {
+    #[serde(with = "fixed_length")]
+    data: [u8; 4096],
+}
+
+#[test]
+fn serialize_no_bytes() {
+    let bytes = NoBytes { data: [] };
+
+    let serialized_bytes = to_bytes(&bytes).unwrap();
+
+    assert!(serialized_bytes.is_empty());
+}
+
+#[test]
+fn deserialize_no_bytes() {
+    let mut cursor = Cursor::new(vec![]);
+
+    let bytes: NoBytes = from_reader(&mut cursor).unwrap();
+
+    assert_eq!(bytes.data, []);
+}
+
+#[test]
+fn serialize_more_than_32_bytes() {
+    let mut data = [0; 65];
+
+    for (index, byte) in data.iter_mut().enumerate() {
+        *byte = index as u8;
+    }
+
+    let serialized_bytes = to_bytes(&SixtyFiveBytes { data }).unwrap();
+
+    let mut expected_bytes = data.to_vec();
+
+    expected_bytes.extend_from_slice(&[0, 0, 0]);
+
+    assert_eq!(serialized_bytes, expected_bytes);
+}
+
+#[test]
+fn deserialize_more_than_32_bytes() {
+    let mut serialized_bytes: Vec<u8> = (0..65).collect();
+
+    serialized_bytes.extend_from_slice(&[0, 0, 0]);
+
+    let mut cursor = Cursor::new(serialized_bytes.clone());
+
+    let bytes: SixtyFiveBytes = from_reader(&mut cursor).unwrap();
+
+    assert_eq!(&bytes.data[..], &serialized_bytes[..65]);
+    assert_eq!(cursor.position(), 68);
+}
+
+#[test]
+fn serialize_deserialize_page() {
+    let mut data = [0; 4096];
+
+    for (index, byte) in data.iter_mut().enumerate() {
+        *byte = (index % 251) as u8;
+    }
+
+    let serialized_bytes = to_bytes(&Page { data }).unwrap();
+
+    assert_eq!(serialized_bytes.len(), 4096);
+
+    let mut cursor = Cursor::new(serialized_bytes);
+
+    let page: Page = from_reader(&mut cursor).unwrap();
+
+    assert_eq!(&page.data[..], &data[..]);
+}
+
+#[test]
+fn deserialize_truncated_bytes() {
+    let mut cursor = Cursor::new(vec![0, 1, 2, 4, 8]);
+
+    let result: Result<SixBytes, _> = from_reader(&mut cursor);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn deserialize_bytes_with_non_zero_padding() {
+    let serialized_bytes = [0, 1, 2, 4, 8, 16, 0, 1];
+
+    let bytes: SixBytes = from_bytes(serialized_bytes).unwrap();
+
+    assert_eq!(bytes.data, [0, 1, 2, 4, 8, 16]);
+}
+
+#[test]
+fn deserialize_bytes_with_non_zero_padding_in_strict_mode() {
+    let serialized_bytes = [0, 1, 2, 4, 8, 16, 0, 1];
+
+    let result: Result<SixBytes, _> = from_bytes_strict(serialized_bytes);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn deserialize_bytes_with_zero_padding_in_strict_mode() {
+    let serialized_bytes = [0, 1, 2, 4, 8, 16, 0, 0];
+
+    let bytes: SixBytes = from_bytes_strict(serialized_bytes).unwrap();
+
+    assert_eq!(bytes.data, [0, 1, 2, 4, 8, 16]);
+}
@@ -0,0 +1,195 @@
+use {
+    crate::{
+        ser::SizeCounter, CompatDeserializationError, CompatSerializationError,
+        Deserializer, Limits, MapEncoding, Serializer, SliceReader,
+        StringEncoding,
+    },
+    byteorder::WriteBytesExt,
+    serde::{Deserialize, Serialize},
+    std::io::{Cursor, Read, Write},
+};
+
+/// Configuration shared by serialization and deserialization calls.
+///
+/// The helper functions like [`to_bytes`](fn.to_bytes.html) and
+/// [`from_bytes`](fn.from_bytes.html) always use the default configuration.
+/// An `Options` instance can be used instead to serialize and deserialize
+/// data with a different configuration, which is applied to each call.
+///
+/// ```
+/// # use serde_xdr::{Limits, Options, StringEncoding};
+/// let options = Options::new()
+///     .with_strict_mode(true)
+///     .with_string_encoding(StringEncoding::Utf8)
+///     .with_limits(Limits::default().with_max_length(1024));
+///
+/// let bytes = options.serialize("caf\u{e9}").unwrap();
+/// let string: String = options.deserialize(&bytes).unwrap();
+///
+/// assert_eq!(string, "caf\u{e9}");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Options {
+    limits: Limits,
+    strict: bool,
+    ignore_enum_variant_names: bool,
+    map_encoding: MapEncoding,
+    string_encoding: Option<StringEncoding>,
+}
+
+impl Options {
+    /// Create the default configuration.
+    pub fn new() -> Self {
+        Options::default()
+    }
+
+    /// Configure the resource limits to enforce while deserializing.
+    ///
+    /// See [`Deserializer::with_limits`][limits].
+    ///
+    /// [limits]: struct.Deserializer.html#method.with_limits
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Enable or disable strict mode when deserializing.
+    ///
+    /// See [`Deserializer::with_strict_mode`][strict].
+    ///
+    /// [strict]: struct.Deserializer.html#method.with_strict_mode
+    pub fn with_strict_mode(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Enable or disable ignoring the names of enum variants when
+    /// deserializing.
+    ///
+    /// See [`Deserializer::with_ignored_enum_variant_names`][names].
+    ///
+    /// [names]: struct.Deserializer.html#method.with_ignored_enum_variant_names
+    pub fn with_ignored_enum_variant_names(mut self, enabled: bool) -> Self {
+        self.ignore_enum_variant_names = enabled;
+        self
+    }
+
+    /// Configure how maps are serialized and deserialized.
+    ///
+    /// See [`MapEncoding`](enum.MapEncoding.html) for the available encodings.
+    pub fn with_map_encoding(mut self, map_encoding: MapEncoding) -> Self {
+        self.map_encoding = map_encoding;
+        self
+    }
+
+    /// Configure how strings and characters are serialized and deserialized.
+    ///
+    /// See [`StringEncoding`](enum.StringEncoding.html) for the available
+    /// encodings.
+    pub fn with_string_encoding(mut self, encoding: StringEncoding) -> Self {
+        self.string_encoding = Some(encoding);
+        self
+    }
+
+    /// Create a serializer with this configuration.
+    pub fn serializer<'w, W>(&self, writer: &'w mut W) -> Serializer<'w, W>
+    where
+        W: WriteBytesExt + 'w,
+    {
+        let serializer =
+            Serializer::new(writer).with_map_encoding(self.map_encoding);
+
+        match self.string_encoding {
+            Some(encoding) => serializer.with_string_encoding(encoding),
+            None => serializer,
+        }
+    }
+
+    /// Create a deserializer with this configuration.
+    pub fn deserializer<'r, R>(&self, reader: &'r mut R) -> Deserializer<'r, R>
+    where
+        R: ?Sized + 'r,
+    {
+        let deserializer = Deserializer::new(reader)
+            .with_limits(self.limits)
+            .with_strict_mode(self.strict)
+            .with_ignored_enum_variant_names(self.ignore_enum_variant_names)
+            .with_map_encoding(self.map_encoding);
+
+        match self.string_encoding {
+            Some(encoding) => deserializer.with_string_encoding(encoding),
+            None => deserializer,
+        }
+    }
+
+    /// Compute the size of the serialized data.
+    pub fn serialized_size<T>(
+        &self,
+        value: &T,
+    ) -> Result<u64, CompatSerializationError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut counter = SizeCounter::default();
+
+        value.serialize(self.serializer(&mut counter).counting())?;
+
+        Ok(counter.size())
+    }
+
+    /// Serialize data into a vector of bytes.
+    pub fn serialize<T>(
+        &self,
+        value: &T,
+    ) -> Result<Vec<u8>, CompatSerializationError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut cursor = Cursor::new(Vec::new());
+
+        value.serialize(self.serializer(&mut cursor).with_back_patching())?;
+
+        Ok(cursor.into_inner())
+    }
+
+    /// Serialize data through a generic writer.
+    pub fn to_writer<W, T>(
+        &self,
+        writer: &mut W,
+        value: &T,
+    ) -> Result<(), CompatSerializationError>
+    where
+        W: Write,
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self.serializer(writer))?;
+
+        Ok(())
+    }
+
+    /// Deserialize data from a slice of bytes, borrowing from it when
+    /// possible.
+    pub fn deserialize<'de, T>(
+        &self,
+        bytes: &'de [u8],
+    ) -> Result<T, CompatDeserializationError>
+    where
+        T: Deserialize<'de>,
+    {
+        let mut reader = SliceReader::new(bytes);
+
+        T::deserialize(&mut self.deserializer(&mut reader))
+    }
+
+    /// Deserialize data from a generic reader.
+    pub fn from_reader<'de, R, T>(
+        &self,
+        reader: &mut R,
+    ) -> Result<T, CompatDeserializationError>
+    where
+        R: Read,
+        T: Deserialize<'de>,
+    {
+        T::deserialize(&mut self.deserializer(reader))
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A step from a value to one of the values it contains.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum PathSegment {
+    /// A field of a structure or of a structure variant.
+    Field {
+        /// The name of the structure.
+        structure: String,
+        /// The name of the field.
+        field: &'static str,
+    },
+
+    /// An element of a sequence or a tuple, by its index.
+    Element(usize),
+
+    /// The key of a map entry, by the index of the entry.
+    Key(usize),
+
+    /// The value of a map entry, by the index of the entry.
+    Value(usize),
+}
+
+/// The location of a value inside the data being serialized or deserialized.
+///
+/// Displayed as the name of the outermost structure followed by the fields and
+/// indices that lead to the value, like `File.entries[3].name`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Path {
+    segments: Vec<PathSegment>,
+}
+
+impl Path {
+    pub(crate) fn new(segments: Vec<PathSegment>) -> Self {
+        Path { segments }
+    }
+
+    /// Retrieve the segments of the path, starting from the outermost value.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Check if the path has no segments, which means it refers to the
+    /// outermost value.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        if let Some(PathSegment::Field { structure, .. }) =
+            self.segments.first()
+        {
+            write!(formatter, "{}", structure)?;
+        }
+
+        for segment in &self.segments {
+            match segment {
+                PathSegment::Field { field, .. } => {
+                    write!(formatter, ".{}", field)?
+                }
+                PathSegment::Element(index) => {
+                    write!(formatter, "[{}]", index)?
+                }
+                PathSegment::Key(index) => {
+                    write!(formatter, "[{}].key", index)?
+                }
+                PathSegment::Value(index) => {
+                    write!(formatter, "[{}].value", index)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+use {
+    crate::opaque_data::fixed_length,
+    serde::{Deserialize, Deserializer, Serialize, Serializer},
+};
+
+const EXPONENT_MASK: u128 = 0x7fff << MANTISSA_BITS;
+const MANTISSA_MASK: u128 = (1 << MANTISSA_BITS) - 1;
+const MANTISSA_BITS: u32 = 112;
+const EXPONENT_BIAS: i32 = 16383;
+
+const F64_MANTISSA_BITS: u32 = 52;
+const F64_MANTISSA_MASK: u64 = (1 << F64_MANTISSA_BITS) - 1;
+const F64_EXPONENT_BIAS: i32 = 1023;
+
+/// A quadruple-precision floating-point number.
+///
+/// Equivalent to the XDR `quadruple` type, which is an IEEE 754 binary128
+/// floating-point number serialized as 16 bytes in big-endian order. Rust has
+/// no native type for it, so the value is kept as its raw bits, which can be
+/// accessed without any loss. Comparisons are made on the raw bits.
+///
+/// Every `f64` can be converted into a `Quadruple` without loss, while the
+/// conversion back into an `f64` either rounds the value or fails if it can't
+/// be represented exactly.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Quadruple(u128);
+
+impl Quadruple {
+    /// Create a quadruple-precision number from its raw bits.
+    pub fn from_bits(bits: u128) -> Self {
+        Quadruple(bits)
+    }
+
+    /// Retrieve the raw bits of the quadruple-precision number.
+    pub fn to_bits(self) -> u128 {
+        self.0
+    }
+
+    /// Create a quadruple-precision number from its big-endian representation.
+    pub fn from_be_bytes(bytes: [u8; 16]) -> Self {
+        Quadruple(u128::from_be_bytes(bytes))
+    }
+
+    /// Retrieve the big-endian representation of the quadruple-precision
+    /// number, which is also its XDR representation.
+    pub fn to_be_bytes(self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+
+    /// Convert into the nearest double-precision number.
+    ///
+    /// Values that are too large become infinite, values that are too small
+    /// become zero, and ties are rounded to even. NaN values remain NaN, and
+    /// keep as much of their payload as fits.
+    pub fn to_f64(self) -> f64 {
+        let sign = ((self.0 >> 127) as u64) << 63;
+        let biased_exponent =
+            ((self.0 & EXPONENT_MASK) >> MANTISSA_BITS) as i32;
+        let mantissa = self.0 & MANTISSA_MASK;
+
+        if biased_exponent == 0x7fff {
+            let payload =
+                (mantissa >> (MANTISSA_BITS - F64_MANTISSA_BITS)) as u64;
+            let payload = if mantissa != 0 && payload == 0 {
+                1 << (F64_MANTISSA_BITS - 1)
+            } else {
+                payload
+            };
+
+            return f64::from_bits(
+                sign | (0x7ff << F64_MANTISSA_BITS) | payload,
+            );
+        }
+
+        let (significand, exponent) = if biased_exponent == 0 {
+            (mantissa, 1 - EXPONENT_BIAS)
+        } else {
+            (
+                mantissa | (1 << MANTISSA_BITS),
+                biased_exponent - EXPONENT_BIAS,
+            )
+        };
+
+        if significand == 0 {
+            return f64::from_bits(sign);
+        }
+
+        let minimum_exponent = 1 - F64_EXPONENT_BIAS;
+        let maximum_exponent = F64_EXPONENT_BIAS;
+
+        if exponent > maximum_exponent {
+            return f64::from_bits(sign | (0x7ff << F64_MANTISSA_BITS));
+        }
+
+        let dropped_bits = MANTISSA_BITS - F64_MANTISSA_BITS;
+
+        if exponent >= minimum_exponent {
+            let mut exponent = exponent;
+            let mut rounded = round_shifted(significand, dropped_bits);
+
+            if rounded == 1 << (F64_MANTISSA_BITS + 1) {
+                rounded >>= 1;
+                exponent += 1;
+
+                if exponent > maximum_exponent {
+                    return f64::from_bits(sign | (0x7ff << F64_MANTISSA_BITS));
+                }
+            }
+
+            let biased_exponent = (exponent + F64_EXPONENT_BIAS) as u64;
+
+            f64::from_bits(
+                sign | (biased_exponent << F64_MANTISSA_BITS)
+                    | (rounded & F64_MANTISSA_MASK),
+            )
+        } else {
+            let extra_bits = (minimum_exponent - exponent) as u32;
+            let dropped_bits = dropped_bits + extra_bits;
+
+            if dropped_bits > MANTISSA_BITS + 1 {
+                return f64::from_bits(sign);
+            }
+
+            // Rounding up to the smallest normal number carries into the
+            // exponent bits, which still results in the correct encoding
+            f64::from_bits(sign | round_shifted(significand, dropped_bits))
+        }
+    }
+
+    /// Convert into a double-precision number, if it can be represented
+    /// exactly.
+    pub fn checked_to_f64(self) -> Option<f64> {
+        let value = self.to_f64();
+
+        if Quadruple::from(value) == self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<f64> for Quadruple {
+    fn from(value: f64) -> Self {
+        let bits = value.to_bits();
+        let sign = ((bits >> 63) as u128) << 127;
+        let biased_exponent = ((bits >> F64_MANTISSA_BITS) & 0x7ff) as i32;
+        let mantissa = (bits & F64_MANTISSA_MASK) as u128;
+        let mantissa_shift = MANTISSA_BITS - F64_MANTISSA_BITS;
+
+        let bits = match biased_exponent {
+            0x7ff => sign | EXPONENT_MASK | (mantissa << mantissa_shift),
+            0 if mantissa == 0 => sign,
+            0 => {
+                let highest_bit = 127 - mantissa.leading_zeros();
+                let exponent = highest_bit as i32
+                    - (F64_EXPONENT_BIAS - 1)
+                    - F64_MANTISSA_BITS as i32;
+                let biased_exponent = (exponent + EXPONENT_BIAS) as u128;
+                let fraction = mantissa & !(1 << highest_bit);
+
+                sign | (biased_exponent << MANTISSA_BITS)
+                    | (fraction << (MANTISSA_BITS - highest_bit))
+            }
+            _ => {
+                let exponent = biased_exponent - F64_EXPONENT_BIAS;
+                let biased_exponent = (exponent + EXPONENT_BIAS) as u128;
+
+                sign | (biased_exponent << MANTISSA_BITS)
+                    | (mantissa << mantissa_shift)
+            }
+        };
+
+        Quadruple(bits)
+    }
+}
+
+impl Serialize for Quadruple {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        fixed_length::serialize(&self.to_be_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Quadruple {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        fixed_length::deserialize(deserializer).map(Quadruple::from_be_bytes)
+    }
+}
+
+/// Shift the significand to the right, rounding the result to the nearest
+/// value, with ties rounded to even.
+fn round_shifted(significand: u128, shift: u32) -> u64 {
+    let kept = significand >> shift;
+    let remainder = significand & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+
+    let rounded = if remainder > half || (remainder == half && kept & 1 == 1) {
+        kept + 1
+    } else {
+        kept
+    };
+
+    rounded as u64
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,149 @@
+use super::{
+    super::{from_bytes, to_bytes},
+    Quadruple,
+};
+
+const ONE: u128 = 0x3fff_0000_0000_0000_0000_0000_0000_0000;
+const MINUS_TWO: u128 = 0xc000_0000_0000_0000_0000_0000_0000_0000;
+const INFINITY: u128 = 0x7fff_0000_0000_0000_0000_0000_0000_0000;
+const NEG_INFINITY: u128 = 0xffff_0000_0000_0000_0000_0000_0000_0000;
+const QUIET_NAN: u128 = 0x7fff_8000_0000_0000_0000_0000_0000_0000;
+const F64_MAX: u128 = 0x43fe_ffff_ffff_ffff_f000_0000_0000_0000;
+const F64_MIN_POSITIVE: u128 = 0x3c01_0000_0000_0000_0000_0000_0000_0000;
+const F64_MIN_SUBNORMAL: u128 = 0x3bcd_0000_0000_0000_0000_0000_0000_0000;
+const MIN_SUBNORMAL: u128 = 0x0000_0000_0000_0000_0000_0000_0000_0001;
+const MAX: u128 = 0x7ffe_ffff_ffff_ffff_ffff_ffff_ffff_ffff;
+
+#[test]
+fn convert_from_f64() {
+    let cases = [
+        (0.0, 0),
+        (-0.0, 1 << 127),
+        (1.0, ONE),
+        (-2.0, MINUS_TWO),
+        (f64::INFINITY, INFINITY),
+        (f64::NEG_INFINITY, NEG_INFINITY),
+        (f64::MAX, F64_MAX),
+        (f64::MIN_POSITIVE, F64_MIN_POSITIVE),
+        (f64::from_bits(1), F64_MIN_SUBNORMAL),
+    ];
+
+    for &(value, bits) in cases.iter() {
+        assert_eq!(Quadruple::from(value).to_bits(), bits, "{:e}", value);
+    }
+}
+
+#[test]
+fn convert_nan_from_f64() {
+    assert_eq!(Quadruple::from(f64::NAN).to_bits(), QUIET_NAN);
+}
+
+#[test]
+fn convert_to_f64() {
+    let cases = [
+        (ONE, 1.0),
+        (MINUS_TWO, -2.0),
+        (INFINITY, f64::INFINITY),
+        (NEG_INFINITY, f64::NEG_INFINITY),
+        (F64_MAX, f64::MAX),
+        (F64_MIN_POSITIVE, f64::MIN_POSITIVE),
+        (F64_MIN_SUBNORMAL, f64::from_bits(1)),
+    ];
+
+    for &(bits, value) in cases.iter() {
+        let quadruple = Quadruple::from_bits(bits);
+
+        assert_eq!(quadruple.to_f64().to_bits(), value.to_bits());
+        assert_eq!(quadruple.checked_to_f64(), Some(value));
+    }
+}
+
+#[test]
+fn convert_nan_to_f64() {
+    let quadruple = Quadruple::from_bits(QUIET_NAN);
+
+    assert_eq!(quadruple.to_f64().to_bits(), f64::NAN.to_bits());
+    assert!(quadruple.checked_to_f64().unwrap().is_nan());
+}
+
+#[test]
+fn nan_payload_that_does_not_fit_is_still_nan() {
+    let quadruple = Quadruple::from_bits(INFINITY | 1);
+
+    assert!(quadruple.to_f64().is_nan());
+    assert_eq!(quadruple.checked_to_f64(), None);
+}
+
+#[test]
+fn too_large_value_is_rounded_to_infinity() {
+    let quadruple = Quadruple::from_bits(MAX);
+
+    assert_eq!(quadruple.to_f64(), f64::INFINITY);
+    assert_eq!(quadruple.checked_to_f64(), None);
+}
+
+#[test]
+fn quadruple_subnormal_is_rounded_to_zero() {
+    let quadruple = Quadruple::from_bits(MIN_SUBNORMAL);
+
+    assert_eq!(quadruple.to_f64().to_bits(), 0);
+    assert_eq!(quadruple.checked_to_f64(), None);
+
+    let negative = Quadruple::from_bits(MIN_SUBNORMAL | 1 << 127);
+
+    assert_eq!(negative.to_f64().to_bits(), (-0.0f64).to_bits());
+}
+
+#[test]
+fn ties_are_rounded_to_even() {
+    let half_ulp = Quadruple::from_bits(ONE | 1 << 59);
+    let above_half_ulp = Quadruple::from_bits(ONE | 1 << 59 | 1);
+    let odd_half_ulp = Quadruple::from_bits(ONE | 1 << 60 | 1 << 59);
+
+    assert_eq!(half_ulp.to_f64(), 1.0);
+    assert_eq!(half_ulp.checked_to_f64(), None);
+    assert_eq!(above_half_ulp.to_f64(), 1.0 + f64::EPSILON);
+    assert_eq!(odd_half_ulp.to_f64(), 1.0 + 2.0 * f64::EPSILON);
+}
+
+#[test]
+fn rounding_to_f64_subnormal() {
+    let half_min_subnormal =
+        Quadruple::from_bits(F64_MIN_SUBNORMAL - (1 << 112));
+    let above_half_min_subnormal =
+        Quadruple::from_bits(F64_MIN_SUBNORMAL - (1 << 112) + 1);
+
+    assert_eq!(half_min_subnormal.to_f64(), 0.0);
+    assert_eq!(above_half_min_subnormal.to_f64(), f64::from_bits(1));
+}
+
+#[test]
+fn rounding_up_to_f64_min_positive() {
+    let just_below_min_positive = Quadruple::from_bits(F64_MIN_POSITIVE - 1);
+
+    assert_eq!(just_below_min_positive.to_f64(), f64::MIN_POSITIVE);
+}
+
+#[test]
+fn serialize_quadruple() {
+    let bytes = to_bytes(&Quadruple::from(1.0)).unwrap();
+
+    let expected_bytes = vec![
+        0x3f, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+    ];
+
+    assert_eq!(bytes, expected_bytes);
+}
+
+#[test]
+fn deserialize_quadruple() {
+    let bytes = [
+        0x7f, 0xff, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x01,
+    ];
+
+    let quadruple: Quadruple = from_bytes(bytes).unwrap();
+
+    assert_eq!(quadruple.to_bits(), QUIET_NAN | 1);
+}
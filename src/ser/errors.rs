@@ -1,4 +1,5 @@
 use {
+    crate::{Path, PathSegment},
     failure::{Compat, Fail},
     serde::ser,
     std::{
@@ -11,6 +12,34 @@ use {
 /// Error during serialization.
 #[derive(Debug, Fail)]
 pub enum SerializationError {
+    /// The buffer is too small to hold the serialized data.
+    #[fail(
+        display = "buffer is too small: {} bytes are required, but only {} \
+                   are available",
+        required, available
+    )]
+    BufferTooSmall {
+        /// The size of the serialized data.
+        required: u64,
+        /// The size of the buffer.
+        available: usize,
+    },
+
+    /// Only ASCII characters can be serialized.
+    #[fail(display = "character is not ASCII: {:?}", value)]
+    CharIsNotAscii {
+        /// The character that can't be represented in ASCII.
+        value: char,
+    },
+
+    /// Only characters with code points up to `U+00FF` can be serialized as
+    /// Latin-1.
+    #[fail(display = "character is not Latin-1: {:?}", value)]
+    CharIsNotLatin1 {
+        /// The character that can't be represented in Latin-1.
+        value: char,
+    },
+
     /// Custom error message.
     #[fail(display = "custom error message: {}", message)]
     Custom {
@@ -20,9 +49,20 @@ pub enum SerializationError {
 
     /// Failure to serialize a value.
     #[fail(display = "failed to serialize {}", what)]
+    #[non_exhaustive]
     Failure {
         /// A description of what was being serialized.
         what: String,
+        /// The position of the value inside the value that contains it, if
+        /// it's part of a path.
+        segment: Option<PathSegment>,
+        /// The number of bytes the serializer had written before the start of
+        /// the value, if known.
+        ///
+        /// The offset is relative to where the writer was when the serializer
+        /// was created, so it's only an offset in the output if the serializer
+        /// started writing at its beginning.
+        offset: Option<u64>,
         /// The error that ocurred during serialization.
         #[cause]
         cause: Box<CompatSerializationError>,
@@ -38,6 +78,22 @@ pub enum SerializationError {
         cause: io::Error,
     },
 
+    /// Attempt to serialize a value that's longer than its maximum length.
+    #[fail(display = "length {} exceeds the maximum of {}", length, maximum)]
+    LengthExceedsMaximum {
+        /// The length of the value.
+        length: usize,
+        /// The maximum length allowed for the type.
+        maximum: u32,
+    },
+
+    /// Fatal error while serializing a map.
+    ///
+    /// This is probably caused by ignoring a previous error, or by serializing
+    /// a value without a key.
+    #[fail(display = "fatal failure while serializing map")]
+    MapFatalError,
+
     /// Map types are not supported by XDR.
     #[fail(display = "XDR does not support a map type")]
     MapIsNotSupported,
@@ -50,6 +106,20 @@ pub enum SerializationError {
         length: usize,
     },
 
+    /// The number of elements serialized differs from the declared length.
+    #[fail(
+        display = "{} declared {} elements but {} were serialized",
+        type_name, declared, actual
+    )]
+    SequenceLengthMismatch {
+        /// The name of the type being serialized.
+        type_name: String,
+        /// The length declared when the serialization started.
+        declared: usize,
+        /// The number of elements that were actually serialized.
+        actual: usize,
+    },
+
     /// Fatal error while serializing a sequence or a tuple.
     ///
     /// This is probably caused by ignoring a previous error.
@@ -68,6 +138,24 @@ pub enum SerializationError {
         name: String,
     },
 
+    /// Attempt to skip a field of an object.
+    ///
+    /// XDR structures have no field names, so the fields are deserialized by
+    /// their position. Skipping a field, for example with
+    /// `#[serde(skip_serializing_if = "...")]`, would misalign all of the
+    /// following fields.
+    #[fail(
+        display = "can't skip field {} of {}, because XDR fields are \
+                   positional",
+        field, name
+    )]
+    StructFieldSkipped {
+        /// The name of the type being serialized.
+        name: String,
+        /// The name of the skipped field.
+        field: &'static str,
+    },
+
     /// Attempt to serialize a sequence that's too long.
     #[fail(display = "sequence is too long to be serialized: {}", length)]
     SequenceTooLong {
@@ -87,6 +175,14 @@ pub enum SerializationError {
         string: String,
     },
 
+    /// Only strings with code points up to `U+00FF` can be serialized as
+    /// Latin-1.
+    #[fail(display = "string is not Latin-1 encoded: {}", string)]
+    StringIsNotLatin1 {
+        /// The string that can't be represented as a Latin-1 string.
+        string: String,
+    },
+
     /// Attempt to serialize a string that's too long.
     #[fail(display = "string is too long: {}", string)]
     StringIsTooLong {
@@ -96,12 +192,62 @@ pub enum SerializationError {
     },
 }
 
+impl SerializationError {
+    /// Retrieve the path to the value that failed to be serialized.
+    pub fn path(&self) -> Path {
+        let mut segments = Vec::new();
+        let mut error = self;
+
+        while let SerializationError::Failure { segment, cause, .. } = error {
+            segments.extend(segment.clone());
+            error = cause.get_ref();
+        }
+
+        Path::new(segments)
+    }
+
+    /// Retrieve the offset in bytes of the innermost value that failed to be
+    /// serialized, if known.
+    ///
+    /// The offset counts the bytes written since the serializer was created.
+    pub fn offset(&self) -> Option<u64> {
+        let mut innermost_offset = None;
+        let mut error = self;
+
+        while let SerializationError::Failure { offset, cause, .. } = error {
+            innermost_offset = offset.or(innermost_offset);
+            error = cause.get_ref();
+        }
+
+        innermost_offset
+    }
+
+    /// Retrieve the error that caused the failure, skipping the errors that
+    /// only describe what was being serialized.
+    pub fn root_cause(&self) -> &SerializationError {
+        let mut error = self;
+
+        while let SerializationError::Failure { cause, .. } = error {
+            error = cause.get_ref();
+        }
+
+        error
+    }
+}
+
 /// An `Error`-compatible wrapper for `SerializationError`.
 ///
 /// Contains helper methods to convert to and from the wrapped type.
 #[derive(Debug)]
 pub struct CompatSerializationError(Compat<SerializationError>);
 
+impl CompatSerializationError {
+    /// Retrieve a reference to the wrapped error.
+    pub fn get_ref(&self) -> &SerializationError {
+        self.0.get_ref()
+    }
+}
+
 impl From<SerializationError> for CompatSerializationError {
     fn from(error: SerializationError) -> Self {
         CompatSerializationError(error.compat())
@@ -110,7 +256,25 @@ impl From<SerializationError> for CompatSerializationError {
 
 impl Display for CompatSerializationError {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        self.0.fmt(formatter)
+        let error = self.get_ref();
+
+        if let SerializationError::Failure { .. } = error {
+            let path = error.path();
+
+            write!(formatter, "{}", error.root_cause())?;
+
+            if !path.is_empty() {
+                write!(formatter, " at {}", path)?;
+            }
+
+            if let Some(offset) = error.offset() {
+                write!(formatter, " (byte offset {})", offset)?;
+            }
+
+            Ok(())
+        } else {
+            self.0.fmt(formatter)
+        }
     }
 }
 
@@ -1,13 +1,18 @@
 use {
+    self::slice_writer::SliceWriter,
+    crate::{MapEncoding, StringEncoding},
     byteorder::WriteBytesExt,
-    serde::ser::{self, Serialize},
+    serde::ser::Serialize,
     std::{
         fmt::Display,
-        io::{self, Write},
+        io::{self, Cursor, Seek, SeekFrom, Write},
     },
 };
 
-pub use self::errors::{CompatSerializationError, Result, SerializationError};
+pub use self::{
+    errors::{CompatSerializationError, Result, SerializationError},
+    size_counter::SizeCounter,
+};
 
 /// Serializer for the XDR format.
 ///
@@ -19,8 +24,37 @@ where
     W: WriteBytesExt + 'w,
 {
     writer: &'w mut W,
+    fixed_length_opaque: bool,
+    length_bound: Option<u32>,
+    map_encoding: MapEncoding,
+    string_encoding: Option<StringEncoding>,
+    deferred_length: DeferredLength<W>,
+    position: u64,
+}
+
+/// How a length prefix is written when the number of items it counts is only
+/// known once they have been serialized.
+enum DeferredLength<W> {
+    /// The items are buffered, and written after the length.
+    Buffered,
+
+    /// A placeholder is written, and replaced with the length by seeking back
+    /// to it.
+    BackPatched(fn(&mut W, SeekFrom) -> io::Result<u64>),
+
+    /// A placeholder is written and never replaced, because only the number
+    /// of bytes written is used.
+    Counted,
+}
+
+impl<W> Clone for DeferredLength<W> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
+impl<W> Copy for DeferredLength<W> {}
+
 impl<'w, W> Serializer<'w, W>
 where
     W: WriteBytesExt + 'w,
@@ -28,7 +62,107 @@ where
     /// Create a new instance that serializes data into the given generic
     /// writer.
     pub fn new(writer: &'w mut W) -> Self {
-        Serializer { writer }
+        Serializer {
+            writer,
+            fixed_length_opaque: false,
+            length_bound: None,
+            map_encoding: MapEncoding::Disabled,
+            string_encoding: None,
+            deferred_length: DeferredLength::Buffered,
+            position: 0,
+        }
+    }
+
+    /// Configure how maps are serialized.
+    ///
+    /// Maps aren't supported by XDR, so by default serializing a map fails.
+    /// See [`MapEncoding`](enum.MapEncoding.html) for the available encodings.
+    pub fn with_map_encoding(mut self, map_encoding: MapEncoding) -> Self {
+        self.map_encoding = map_encoding;
+        self
+    }
+
+    /// Configure how strings and characters are serialized.
+    ///
+    /// If no encoding is configured, only ASCII strings can be serialized.
+    /// See [`StringEncoding`](enum.StringEncoding.html) for the available
+    /// encodings.
+    pub fn with_string_encoding(mut self, encoding: StringEncoding) -> Self {
+        self.string_encoding = Some(encoding);
+        self
+    }
+
+    /// Write the lengths of sequences and maps of unknown length by seeking
+    /// back to them.
+    ///
+    /// The length of a sequence is written before its elements, so by default
+    /// the elements of a sequence whose length isn't known in advance are
+    /// buffered until the end. With a writer that implements `Seek`, a
+    /// placeholder is written instead, and replaced with the length once all
+    /// elements have been written.
+    pub fn with_back_patching(mut self) -> Self
+    where
+        W: Seek,
+    {
+        self.deferred_length = DeferredLength::BackPatched(W::seek);
+        self
+    }
+
+    /// Create a serializer with the same configuration that writes into
+    /// another writer.
+    ///
+    /// The position of the nested serializer starts at the given position, so
+    /// that the offsets in errors refer to where the data will be placed in
+    /// the final output.
+    fn nested<'v, V>(
+        &self,
+        writer: &'v mut V,
+        position: u64,
+    ) -> Serializer<'v, V>
+    where
+        V: WriteBytesExt + 'v,
+    {
+        let mut serializer =
+            Serializer::new(writer).with_map_encoding(self.map_encoding);
+
+        serializer.string_encoding = self.string_encoding;
+        serializer.position = position;
+        serializer
+    }
+
+    /// Account for bytes written directly to the writer.
+    fn advance(mut self, count: usize) -> Self {
+        self.position += count as u64;
+        self
+    }
+
+    fn ensure_length_is_within_bound(&mut self, length: usize) -> Result<()> {
+        if let Some(maximum) = self.length_bound.take() {
+            ensure!(
+                length <= maximum as usize,
+                SerializationError::LengthExceedsMaximum { length, maximum }
+            );
+        }
+
+        Ok(())
+    }
+
+    fn write_opaque(self, value: &[u8]) -> Result<Self> {
+        let length = value.len();
+
+        let full_padding = [0u8; 3];
+        let padding_size = (4 - length % 4) % 4;
+        let (padding, _) = full_padding.split_at(padding_size);
+
+        self.writer
+            .write_all(value)
+            .map_err(|error| Self::serialize_opaque_io_error(length, error))?;
+
+        self.writer
+            .write_all(padding)
+            .map_err(|error| Self::serialize_opaque_io_error(length, error))?;
+
+        Ok(self.advance(length + padding_size))
     }
 
     fn serialize_failure<T, S>(
@@ -42,6 +176,8 @@ where
     {
         SerializationError::Failure {
             what: format!("a value {} of type {}", value, type_name),
+            segment: None,
+            offset: None,
             cause: Box::new(error.into()),
         }
         .into()
@@ -56,6 +192,8 @@ where
     {
         SerializationError::Failure {
             what: format!("opaque data of length {}", length),
+            segment: None,
+            offset: None,
             cause: Box::new(error.into()),
         }
     }
@@ -85,32 +223,34 @@ where
     }
 }
 
+impl<'w> Serializer<'w, SizeCounter> {
+    /// Count the size of the data without buffering anything.
+    ///
+    /// The lengths of sequences and maps of unknown length are counted as
+    /// placeholders, and maps aren't sorted, because neither changes the size
+    /// of the data.
+    pub(crate) fn counting(mut self) -> Self {
+        self.deferred_length = DeferredLength::Counted;
+        self
+    }
+}
+
 mod serializer;
 
-impl<'w, W> ser::SerializeMap for Serializer<'w, W>
+/// Compute the size of the serialized data.
+///
+/// Returns the number of bytes that serializing the value would produce,
+/// following the same rules as [`to_bytes`](fn.to_bytes.html), without
+/// writing or buffering the data anywhere.
+pub fn serialized_size<T>(value: &T) -> Result<u64>
 where
-    W: WriteBytesExt + 'w,
+    T: Serialize,
 {
-    type Ok = Self;
-    type Error = CompatSerializationError;
+    let mut counter = SizeCounter::default();
 
-    fn serialize_key<T>(&mut self, _key: &T) -> Result<()>
-    where
-        T: ?Sized + Serialize,
-    {
-        unreachable!("map is not supported")
-    }
+    value.serialize(Serializer::new(&mut counter).counting())?;
 
-    fn serialize_value<T>(&mut self, _value: &T) -> Result<()>
-    where
-        T: ?Sized + Serialize,
-    {
-        unreachable!("map is not supported")
-    }
-
-    fn end(self) -> Result<Self> {
-        unreachable!("map is not supported")
-    }
+    Ok(counter.size())
 }
 
 /// Serialize data into a vector of bytes.
@@ -120,11 +260,44 @@ pub fn to_bytes<T>(value: &T) -> Result<Vec<u8>>
 where
     T: Serialize,
 {
-    let mut bytes = Vec::new();
+    let mut cursor = Cursor::new(Vec::new());
+
+    value.serialize(Serializer::new(&mut cursor).with_back_patching())?;
+
+    Ok(cursor.into_inner())
+}
+
+/// Serialize data into a slice of bytes.
+///
+/// Serializes a generic data type into the start of a borrowed buffer, and
+/// returns the number of bytes that were written. If the buffer is too small,
+/// the serialization stops at the first write that doesn't fit, leaving the
+/// contents of the buffer unspecified, and a `BufferTooSmall` error reports the
+/// required size.
+pub fn to_slice<T>(value: &T, buffer: &mut [u8]) -> Result<usize>
+where
+    T: Serialize,
+{
+    let available = buffer.len();
+    let mut writer = SliceWriter::new(buffer);
+    let result = value
+        .serialize(Serializer::new(&mut writer).with_back_patching())
+        .map(|_| ());
 
-    value.serialize(Serializer::new(&mut bytes))?;
+    match result {
+        Ok(_) => Ok(writer.written()),
+        Err(_) if writer.overflowed() => {
+            // Only the failure path pays for a second pass, to report the
+            // size the buffer should have had
+            let required = serialized_size(value)?;
 
-    Ok(bytes)
+            bail!(SerializationError::BufferTooSmall {
+                required,
+                available,
+            })
+        }
+        Err(error) => Err(error),
+    }
 }
 
 /// Serialize data through a generic writer.
@@ -142,3 +315,5 @@ where
 }
 
 mod errors;
+mod size_counter;
+mod slice_writer;
@@ -0,0 +1,250 @@
+use {
+    super::{
+        super::{
+            errors::{CompatSerializationError, Result, SerializationError},
+            DeferredLength, Serializer,
+        },
+        pending_length::PendingLength,
+    },
+    crate::{MapEncoding, PathSegment},
+    byteorder::WriteBytesExt,
+    serde::{
+        ser::{Serialize, SerializeMap},
+        Serializer as SerdeSerializer,
+    },
+};
+
+pub struct MapSerializer<'w, W>
+where
+    W: WriteBytesExt + 'w,
+{
+    serializer: Option<Serializer<'w, W>>,
+    sorted_entries: Option<Vec<(Vec<u8>, Vec<u8>)>>,
+    pending_length: Option<PendingLength<W>>,
+    pending_key: Option<Vec<u8>>,
+    current_index: usize,
+}
+
+impl<'w, W> MapSerializer<'w, W>
+where
+    W: WriteBytesExt + 'w,
+{
+    pub fn start_map(
+        length: Option<usize>,
+        mut serializer: Serializer<'w, W>,
+    ) -> Result<Self> {
+        let mut pending_length = None;
+
+        let sorted = match serializer.map_encoding {
+            MapEncoding::Disabled => {
+                bail!(SerializationError::MapIsNotSupported)
+            }
+            MapEncoding::Unsorted => false,
+            // The order of the entries doesn't change the size of the map
+            MapEncoding::Sorted => {
+                !matches!(serializer.deferred_length, DeferredLength::Counted)
+            }
+        };
+
+        let sorted_entries = if sorted {
+            Some(Vec::with_capacity(length.unwrap_or(0)))
+        } else {
+            if let Some(length) = length {
+                serializer.ensure_length_is_within_bound(length)?;
+                serializer = Self::serialize_length(length, serializer)?;
+            } else {
+                // Like for sequences, the length prefix is either
+                // back-patched or written before the buffered entries
+                let (started, pending) =
+                    PendingLength::start(serializer, "map")?;
+
+                serializer = started;
+                pending_length = Some(pending);
+            }
+
+            None
+        };
+
+        Ok(MapSerializer {
+            serializer: Some(serializer),
+            sorted_entries,
+            pending_length,
+            pending_key: None,
+            current_index: 0,
+        })
+    }
+
+    fn serialize_length(
+        length: usize,
+        serializer: Serializer<'w, W>,
+    ) -> Result<Serializer<'w, W>> {
+        ensure!(
+            length <= u32::MAX as usize,
+            SerializationError::SequenceTooLong { length }
+        );
+
+        serializer.serialize_u32(length as u32).map_err(|error| {
+            SerializationError::Failure {
+                what: format!("map length: {}", length),
+                segment: None,
+                offset: None,
+                cause: Box::new(error),
+            }
+            .into()
+        })
+    }
+
+    fn serialize_item<T>(&mut self, item: &T, part: MapPart) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let serializer = match self.serializer.take() {
+            Some(serializer) => serializer,
+            None => bail!(SerializationError::MapFatalError),
+        };
+
+        let offset = serializer.position;
+
+        let mut pending_length = self.pending_length.take();
+
+        if self.sorted_entries.is_some() {
+            // The entries are only placed once they are sorted, so the
+            // offsets of their contents are reported from the start of the map
+            let mut buffer = Vec::new();
+            let result = item.serialize(serializer.nested(&mut buffer, offset));
+
+            self.serializer = Some(serializer);
+
+            result.map_err(|error| self.failure(part, offset, error))?;
+            self.store_sorted_item(buffer);
+        } else if let Some(buffer) =
+            pending_length.as_mut().and_then(PendingLength::buffer)
+        {
+            // The buffered entries are written after the length
+            let offset = offset + 4 + buffer.len() as u64;
+            let result = item.serialize(serializer.nested(buffer, offset));
+
+            self.serializer = Some(serializer);
+
+            result.map_err(|error| self.failure(part, offset, error))?;
+        } else {
+            let serializer = item
+                .serialize(serializer)
+                .map_err(|error| self.failure(part, offset, error))?;
+
+            self.serializer = Some(serializer);
+        }
+
+        self.pending_length = pending_length;
+
+        Ok(())
+    }
+
+    fn store_sorted_item(&mut self, buffer: Vec<u8>) {
+        match self.pending_key.take() {
+            None => self.pending_key = Some(buffer),
+            Some(key) => {
+                if let Some(entries) = self.sorted_entries.as_mut() {
+                    entries.push((key, buffer));
+                }
+            }
+        }
+    }
+
+    fn failure<E>(
+        &self,
+        part: MapPart,
+        offset: u64,
+        error: E,
+    ) -> SerializationError
+    where
+        E: Into<CompatSerializationError>,
+    {
+        let index = self.current_index;
+        let (name, segment) = match part {
+            MapPart::Key => ("key", PathSegment::Key(index)),
+            MapPart::Value => ("value", PathSegment::Value(index)),
+        };
+
+        SerializationError::Failure {
+            what: format!("{} of map entry {}", name, index),
+            segment: Some(segment),
+            offset: Some(offset),
+            cause: Box::new(error.into()),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum MapPart {
+    Key,
+    Value,
+}
+
+impl<'w, W> SerializeMap for MapSerializer<'w, W>
+where
+    W: WriteBytesExt + 'w,
+{
+    type Ok = Serializer<'w, W>;
+    type Error = CompatSerializationError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_item(key, MapPart::Key)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_item(value, MapPart::Value)?;
+        self.current_index += 1;
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Serializer<'w, W>> {
+        let serializer = match self.serializer {
+            Some(serializer) if self.pending_key.is_none() => serializer,
+            _ => bail!(SerializationError::MapFatalError),
+        };
+
+        let mut entries = match self.sorted_entries {
+            Some(entries) => entries,
+            None => {
+                return match self.pending_length {
+                    Some(pending_length) => pending_length.finish(
+                        self.current_index,
+                        "map entries",
+                        serializer,
+                    ),
+                    None => Ok(serializer),
+                }
+            }
+        };
+
+        entries.sort_by(|(key, _), (other_key, _)| key.cmp(other_key));
+
+        let mut serializer = Self::serialize_length(entries.len(), serializer)?;
+
+        for (key, value) in entries {
+            serializer
+                .writer
+                .write_all(&key)
+                .and_then(|_| serializer.writer.write_all(&value))
+                .map_err(|error| SerializationError::IoError {
+                    what: "map entry".to_string(),
+                    cause: error,
+                })?;
+
+            serializer = serializer.advance(key.len() + value.len());
+        }
+
+        Ok(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests;
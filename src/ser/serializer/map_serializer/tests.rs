@@ -0,0 +1,156 @@
+use {
+    super::super::{
+        super::{SerializationError, Serializer},
+        tests::*,
+    },
+    crate::MapEncoding,
+    serde::ser::{SerializeMap as _, Serializer as _},
+    std::{collections::BTreeMap, io::Cursor},
+};
+
+#[test]
+fn map_is_not_supported_by_default() {
+    let mut buffer = Vec::new();
+
+    let result = Serializer::new(&mut buffer).serialize_map(Some(0));
+
+    match result.map(|_| ()).map_err(SerializationError::from) {
+        Err(SerializationError::MapIsNotSupported) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn serialize_unsorted_map() {
+    let mut buffer = Vec::new();
+
+    {
+        let mut map_serializer = Serializer::new(&mut buffer)
+            .with_map_encoding(MapEncoding::Unsorted)
+            .serialize_map(Some(2))
+            .unwrap();
+
+        map_serializer.serialize_entry("b", &2u32).unwrap();
+        map_serializer.serialize_entry("a", &1u32).unwrap();
+        map_serializer.end().unwrap();
+    }
+
+    let mut expected_bytes = bytes_of(2);
+
+    expected_bytes.append(&mut bytes_of_str("b", 3));
+    expected_bytes.append(&mut bytes_of(2));
+    expected_bytes.append(&mut bytes_of_str("a", 3));
+    expected_bytes.append(&mut bytes_of(1));
+
+    assert_eq!(buffer, expected_bytes);
+}
+
+fn unsorted_map_with_unknown_length_bytes() -> Vec<u8> {
+    let mut expected_bytes = bytes_of(2);
+
+    expected_bytes.append(&mut bytes_of_str("b", 3));
+    expected_bytes.append(&mut bytes_of(2));
+    expected_bytes.append(&mut bytes_of_str("a", 3));
+    expected_bytes.append(&mut bytes_of(1));
+    expected_bytes
+}
+
+#[test]
+fn serialize_unsorted_map_with_unknown_length() {
+    let mut buffer = Vec::new();
+
+    {
+        let mut map_serializer = Serializer::new(&mut buffer)
+            .with_map_encoding(MapEncoding::Unsorted)
+            .serialize_map(None)
+            .unwrap();
+
+        map_serializer.serialize_entry("b", &2u32).unwrap();
+        map_serializer.serialize_entry("a", &1u32).unwrap();
+        map_serializer.end().unwrap();
+    }
+
+    assert_eq!(buffer, unsorted_map_with_unknown_length_bytes());
+}
+
+#[test]
+fn back_patch_unsorted_map_with_unknown_length() {
+    let mut cursor = Cursor::new(vec![0xff; 4]);
+
+    cursor.set_position(4);
+
+    {
+        let mut map_serializer = Serializer::new(&mut cursor)
+            .with_map_encoding(MapEncoding::Unsorted)
+            .with_back_patching()
+            .serialize_map(None)
+            .unwrap();
+
+        map_serializer.serialize_entry("b", &2u32).unwrap();
+        map_serializer.serialize_entry("a", &1u32).unwrap();
+        map_serializer.end().unwrap();
+    }
+
+    let mut expected_bytes = vec![0xff; 4];
+
+    expected_bytes.append(&mut unsorted_map_with_unknown_length_bytes());
+
+    assert_eq!(cursor.position(), expected_bytes.len() as u64);
+    assert_eq!(cursor.into_inner(), expected_bytes);
+}
+
+#[test]
+fn serialize_sorted_map() {
+    let mut buffer = Vec::new();
+
+    {
+        let mut map_serializer = Serializer::new(&mut buffer)
+            .with_map_encoding(MapEncoding::Sorted)
+            .serialize_map(None)
+            .unwrap();
+
+        map_serializer.serialize_entry(&3u32, "three").unwrap();
+        map_serializer.serialize_entry(&1u32, "one").unwrap();
+        map_serializer.serialize_entry(&2u32, "two").unwrap();
+        map_serializer.end().unwrap();
+    }
+
+    let mut expected_bytes = bytes_of(3);
+
+    expected_bytes.append(&mut bytes_of(1));
+    expected_bytes.append(&mut bytes_of_str("one", 1));
+    expected_bytes.append(&mut bytes_of(2));
+    expected_bytes.append(&mut bytes_of_str("two", 1));
+    expected_bytes.append(&mut bytes_of(3));
+    expected_bytes.append(&mut bytes_of_str("three", 3));
+
+    assert_eq!(buffer, expected_bytes);
+}
+
+#[test]
+fn sorted_map_keeps_nested_configuration() {
+    let mut buffer = Vec::new();
+
+    {
+        let mut map_serializer = Serializer::new(&mut buffer)
+            .with_map_encoding(MapEncoding::Sorted)
+            .serialize_map(Some(1))
+            .unwrap();
+
+        let mut nested_map = BTreeMap::new();
+
+        nested_map.insert(2u32, 3u32);
+
+        map_serializer.serialize_entry(&1u32, &nested_map).unwrap();
+        map_serializer.end().unwrap();
+    }
+
+    let mut expected_bytes = bytes_of(1);
+
+    expected_bytes.append(&mut bytes_of(1));
+    expected_bytes.append(&mut bytes_of(1));
+    expected_bytes.append(&mut bytes_of(2));
+    expected_bytes.append(&mut bytes_of(3));
+
+    assert_eq!(buffer, expected_bytes);
+}
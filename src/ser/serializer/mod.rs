@@ -1,12 +1,13 @@
 use {
     self::{
-        sequence_serializer::SequenceSerializer,
+        map_serializer::MapSerializer, sequence_serializer::SequenceSerializer,
         struct_serializer::StructSerializer,
     },
     super::{
         errors::{CompatSerializationError, Result, SerializationError},
         Serializer,
     },
+    crate::{bounded, opaque_data::fixed_length, StringEncoding},
     byteorder::{BigEndian, WriteBytesExt},
     serde::ser::{self, Serialize},
 };
@@ -22,7 +23,7 @@ where
     type SerializeTuple = SequenceSerializer<'w, W>;
     type SerializeTupleStruct = SequenceSerializer<'w, W>;
     type SerializeTupleVariant = SequenceSerializer<'w, W>;
-    type SerializeMap = Self;
+    type SerializeMap = MapSerializer<'w, W>;
     type SerializeStruct = StructSerializer<'w, W>;
     type SerializeStructVariant = StructSerializer<'w, W>;
 
@@ -44,7 +45,7 @@ where
             .write_i32::<BigEndian>(value)
             .map_err(|error| Self::io_error("integer", value, error))?;
 
-        Ok(self)
+        Ok(self.advance(4))
     }
 
     fn serialize_i64(self, value: i64) -> Result<Self> {
@@ -52,7 +53,7 @@ where
             .write_i64::<BigEndian>(value)
             .map_err(|error| Self::io_error("hyper integer", value, error))?;
 
-        Ok(self)
+        Ok(self.advance(8))
     }
 
     fn serialize_u8(self, value: u8) -> Result<Self> {
@@ -68,7 +69,7 @@ where
             Self::io_error("unsigned integer", value, error)
         })?;
 
-        Ok(self)
+        Ok(self.advance(4))
     }
 
     fn serialize_u64(self, value: u64) -> Result<Self> {
@@ -76,7 +77,7 @@ where
             Self::io_error("unsigned hyper integer", value, error)
         })?;
 
-        Ok(self)
+        Ok(self.advance(8))
     }
 
     fn serialize_f32(self, value: f32) -> Result<Self> {
@@ -84,7 +85,7 @@ where
             .write_f32::<BigEndian>(value)
             .map_err(|error| Self::io_error("float", value, error))?;
 
-        Ok(self)
+        Ok(self.advance(4))
     }
 
     fn serialize_f64(self, value: f64) -> Result<Self> {
@@ -92,61 +93,81 @@ where
             .write_f64::<BigEndian>(value)
             .map_err(|error| Self::io_error("double", value, error))?;
 
-        Ok(self)
+        Ok(self.advance(8))
     }
 
     fn serialize_char(self, value: char) -> Result<Self> {
+        match self.string_encoding {
+            Some(StringEncoding::Ascii) => ensure!(
+                value.is_ascii(),
+                SerializationError::CharIsNotAscii { value }
+            ),
+            Some(StringEncoding::Latin1) => ensure!(
+                value as u32 <= 0xff,
+                SerializationError::CharIsNotLatin1 { value }
+            ),
+            Some(StringEncoding::Utf8) | None => {}
+        }
+
         self.writer
             .write_u32::<BigEndian>(value as u32)
             .map_err(|error| Self::io_error("char", value, error))?;
 
-        Ok(self)
+        Ok(self.advance(4))
     }
 
-    fn serialize_str(self, value: &str) -> Result<Self> {
-        if value.len() > u32::max_value() as usize {
-            let string = value.to_string();
+    fn serialize_str(mut self, value: &str) -> Result<Self> {
+        let latin1_bytes;
+        let bytes = match self.string_encoding {
+            Some(StringEncoding::Ascii) | None => {
+                if !value.is_ascii() {
+                    let string = value.to_string();
 
-            bail!(SerializationError::StringIsTooLong { string });
-        }
+                    bail!(SerializationError::StringIsNotAscii { string });
+                }
+
+                value.as_bytes()
+            }
+            Some(StringEncoding::Utf8) => value.as_bytes(),
+            Some(StringEncoding::Latin1) => {
+                latin1_bytes = encode_latin1(value)?;
+                &latin1_bytes
+            }
+        };
+
+        self.ensure_length_is_within_bound(bytes.len())?;
 
-        if !value.is_ascii() {
+        if bytes.len() > u32::MAX as usize {
             let string = value.to_string();
 
-            bail!(SerializationError::StringIsNotAscii { string });
+            bail!(SerializationError::StringIsTooLong { string });
         }
 
-        self.serialize_bytes(value.as_bytes())
+        self.serialize_bytes(bytes)
             .map_err(|error| Self::serialize_failure("string", value, error))
     }
 
-    fn serialize_bytes(self, value: &[u8]) -> Result<Self> {
+    fn serialize_bytes(mut self, value: &[u8]) -> Result<Self> {
         let length = value.len();
 
+        if self.fixed_length_opaque {
+            self.fixed_length_opaque = false;
+
+            return self.write_opaque(value);
+        }
+
+        self.ensure_length_is_within_bound(length)?;
+
         ensure!(
             length <= u32::max_value() as usize,
             SerializationError::OpaqueDataIsTooLong { length }
         );
 
-        let full_padding = [0u8; 3];
-        let padding_size = 4 - (length + 3) % 4 - 1;
-        let (padding, _) = full_padding.split_at(padding_size);
-
         let serializer = self
             .serialize_u32(length as u32)
             .map_err(|error| Self::serialize_opaque_failure(length, error))?;
 
-        serializer
-            .writer
-            .write_all(value)
-            .map_err(|error| Self::serialize_opaque_io_error(length, error))?;
-
-        serializer
-            .writer
-            .write_all(padding)
-            .map_err(|error| Self::serialize_opaque_io_error(length, error))?;
-
-        Ok(serializer)
+        serializer.write_opaque(value)
     }
 
     fn serialize_none(self) -> Result<Self> {
@@ -185,6 +206,8 @@ where
         self.serialize_u32(variant_index).map_err(|error| {
             SerializationError::Failure {
                 what: format!("enum variant {}::{}", name, variant),
+                segment: None,
+                offset: None,
                 cause: Box::new(error.into()),
             }
             .into()
@@ -192,20 +215,27 @@ where
     }
 
     fn serialize_newtype_struct<T>(
-        self,
+        mut self,
         name: &'static str,
         value: &T,
     ) -> Result<Self>
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(self).map_err(|error| {
+        self.fixed_length_opaque = name == fixed_length::TYPE_NAME;
+
+        let mut serializer = value.serialize(self).map_err(|error| {
             SerializationError::Failure {
                 what: format!("struct {}", name),
+                segment: None,
+                offset: None,
                 cause: Box::new(error.into()),
             }
-            .into()
-        })
+        })?;
+
+        serializer.fixed_length_opaque = false;
+
+        Ok(serializer)
     }
 
     fn serialize_newtype_variant<T>(
@@ -222,6 +252,8 @@ where
             self.serialize_u32(variant_index).map_err(|error| {
                 SerializationError::Failure {
                     what: format!("union variant {}::{}", name, variant),
+                    segment: None,
+                    offset: None,
                     cause: Box::new(error.into()),
                 }
             })?;
@@ -229,6 +261,8 @@ where
         value.serialize(serializer).map_err(|error| {
             SerializationError::Failure {
                 what: format!("union variant {}::{}", name, variant),
+                segment: None,
+                offset: None,
                 cause: Box::new(error.into()),
             }
             .into()
@@ -242,16 +276,22 @@ where
         Ok(SequenceSerializer::start_sequence(length, self)?)
     }
 
-    fn serialize_tuple(self, _length: usize) -> Result<Self::SerializeTuple> {
-        Ok(SequenceSerializer::start_tuple(self))
+    fn serialize_tuple(self, length: usize) -> Result<Self::SerializeTuple> {
+        Ok(SequenceSerializer::start_tuple(length, self))
     }
 
     fn serialize_tuple_struct(
-        self,
+        mut self,
         name: &'static str,
-        _length: usize,
+        length: usize,
     ) -> Result<Self::SerializeTupleStruct> {
-        Ok(SequenceSerializer::start_tuple_struct(name, self))
+        if name == bounded::TYPE_NAME {
+            self.length_bound = Some(length.min(u32::MAX as usize) as u32);
+
+            return Ok(SequenceSerializer::start_bounded(self));
+        }
+
+        Ok(SequenceSerializer::start_tuple_struct(name, length, self))
     }
 
     fn serialize_tuple_variant(
@@ -259,26 +299,28 @@ where
         name: &'static str,
         variant_index: u32,
         variant: &'static str,
-        _length: usize,
+        length: usize,
     ) -> Result<Self::SerializeTupleVariant> {
         let serializer =
             self.serialize_u32(variant_index).map_err(|error| {
                 SerializationError::Failure {
                     what: format!("tuple variant {}::{}", name, variant),
+                    segment: None,
+                    offset: None,
                     cause: Box::new(error.into()),
                 }
             })?;
 
         Ok(SequenceSerializer::start_tuple_variant(
-            name, variant, serializer,
+            name, variant, length, serializer,
         ))
     }
 
     fn serialize_map(
         self,
-        _length: Option<usize>,
+        length: Option<usize>,
     ) -> Result<Self::SerializeMap> {
-        bail!(SerializationError::MapIsNotSupported)
+        MapSerializer::start_map(length, self)
     }
 
     fn serialize_struct(
@@ -300,6 +342,8 @@ where
             self.serialize_u32(variant_index).map_err(|error| {
                 SerializationError::Failure {
                     what: format!("struct variant {}::{}", name, variant),
+                    segment: None,
+                    offset: None,
                     cause: Box::new(error.into()),
                 }
             })?;
@@ -310,6 +354,23 @@ where
     }
 }
 
+fn encode_latin1(string: &str) -> Result<Vec<u8>> {
+    string
+        .chars()
+        .map(|character| {
+            if character as u32 <= 0xff {
+                Ok(character as u8)
+            } else {
+                let string = string.to_string();
+
+                Err(SerializationError::StringIsNotLatin1 { string }.into())
+            }
+        })
+        .collect()
+}
+
+mod map_serializer;
+mod pending_length;
 mod sequence_serializer;
 mod struct_serializer;
 
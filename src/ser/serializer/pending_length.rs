@@ -0,0 +1,135 @@
+use {
+    super::super::{
+        errors::{Result, SerializationError},
+        DeferredLength, Serializer,
+    },
+    byteorder::{BigEndian, WriteBytesExt},
+    std::io::{self, SeekFrom},
+};
+
+/// The length prefix of items whose number is only known once they have all
+/// been serialized.
+pub struct PendingLength<W> {
+    maximum: Option<u32>,
+    state: State<W>,
+}
+
+enum State<W> {
+    /// The items are buffered, to be written after the length.
+    Buffered(Vec<u8>),
+
+    /// A placeholder was written at the given position of the writer, and is
+    /// replaced with the length by seeking back to it.
+    BackPatched {
+        seek: fn(&mut W, SeekFrom) -> io::Result<u64>,
+        start: u64,
+    },
+
+    /// A placeholder was written, and is never replaced.
+    Counted,
+}
+
+impl<W> PendingLength<W>
+where
+    W: WriteBytesExt,
+{
+    /// Prepare to write the length, either by writing a placeholder or by
+    /// preparing a buffer for the items.
+    pub fn start<'w>(
+        mut serializer: Serializer<'w, W>,
+        what: &str,
+    ) -> Result<(Serializer<'w, W>, Self)> {
+        // The bound of a bounded type only applies to the length, and not to
+        // the items that are serialized before it's known
+        let maximum = serializer.length_bound.take();
+
+        let state = match serializer.deferred_length {
+            DeferredLength::Buffered => State::Buffered(Vec::new()),
+            DeferredLength::BackPatched(seek) => {
+                let start = seek(serializer.writer, SeekFrom::Current(0))
+                    .and_then(|start| {
+                        serializer.writer.write_u32::<BigEndian>(0)?;
+                        Ok(start)
+                    })
+                    .map_err(|error| Self::io_error(what, error))?;
+
+                serializer = serializer.advance(4);
+
+                State::BackPatched { seek, start }
+            }
+            DeferredLength::Counted => {
+                serializer
+                    .writer
+                    .write_u32::<BigEndian>(0)
+                    .map_err(|error| Self::io_error(what, error))?;
+
+                serializer = serializer.advance(4);
+
+                State::Counted
+            }
+        };
+
+        Ok((serializer, PendingLength { maximum, state }))
+    }
+
+    /// Retrieve the buffer for the items, if they must be buffered.
+    pub fn buffer(&mut self) -> Option<&mut Vec<u8>> {
+        match self.state {
+            State::Buffered(ref mut buffer) => Some(buffer),
+            State::BackPatched { .. } | State::Counted => None,
+        }
+    }
+
+    /// Write the length once the number of items is known, followed by the
+    /// items if they were buffered.
+    pub fn finish<'w>(
+        self,
+        length: usize,
+        what: &str,
+        serializer: Serializer<'w, W>,
+    ) -> Result<Serializer<'w, W>> {
+        if let Some(maximum) = self.maximum {
+            ensure!(
+                length <= maximum as usize,
+                SerializationError::LengthExceedsMaximum { length, maximum }
+            );
+        }
+
+        ensure!(
+            length <= u32::MAX as usize,
+            SerializationError::SequenceTooLong { length }
+        );
+
+        let writer = &mut *serializer.writer;
+
+        match self.state {
+            State::Buffered(buffer) => {
+                writer
+                    .write_u32::<BigEndian>(length as u32)
+                    .and_then(|_| writer.write_all(&buffer))
+                    .map_err(|error| Self::io_error(what, error))?;
+
+                Ok(serializer.advance(4 + buffer.len()))
+            }
+            State::BackPatched { seek, start } => {
+                seek(writer, SeekFrom::Current(0))
+                    .and_then(|end| {
+                        seek(writer, SeekFrom::Start(start))?;
+                        writer.write_u32::<BigEndian>(length as u32)?;
+                        seek(writer, SeekFrom::Start(end))
+                    })
+                    .map_err(|error| Self::io_error(what, error))?;
+
+                Ok(serializer)
+            }
+            State::Counted => Ok(serializer),
+        }
+    }
+
+    fn io_error(what: &str, error: io::Error) -> SerializationError {
+        SerializationError::IoError {
+            what: format!("length of {}", what),
+            cause: error,
+        }
+    }
+}
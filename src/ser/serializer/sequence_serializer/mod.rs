@@ -1,9 +1,13 @@
 use {
     self::type_name::TypeName,
-    super::super::{
-        errors::{CompatSerializationError, Result, SerializationError},
-        Serializer,
+    super::{
+        super::{
+            errors::{CompatSerializationError, Result, SerializationError},
+            Serializer,
+        },
+        pending_length::PendingLength,
     },
+    crate::PathSegment,
     byteorder::WriteBytesExt,
     serde::{
         ser::{
@@ -20,18 +24,26 @@ where
 {
     type_name: TypeName,
     serializer: Option<Serializer<'w, W>>,
+    length: Option<usize>,
     current_index: usize,
+    pending_length: Option<PendingLength<W>>,
 }
 
 impl<'w, W> SequenceSerializer<'w, W>
 where
     W: WriteBytesExt + 'w,
 {
-    pub fn new(type_name: TypeName, serializer: Serializer<'w, W>) -> Self {
+    pub fn new(
+        type_name: TypeName,
+        length: Option<usize>,
+        serializer: Serializer<'w, W>,
+    ) -> Self {
         SequenceSerializer {
             type_name,
             serializer: Some(serializer),
+            length,
             current_index: 0,
+            pending_length: None,
         }
     }
 
@@ -40,32 +52,58 @@ where
         mut serializer: Serializer<'w, W>,
     ) -> Result<Self> {
         if let Some(length) = length {
+            serializer.ensure_length_is_within_bound(length)?;
             serializer = Self::serialize_length(length, serializer)?;
+
+            Ok(SequenceSerializer::new(
+                TypeName::Sequence,
+                Some(length),
+                serializer,
+            ))
         } else {
-            bail!(SerializationError::SequenceWithUnknownLength);
+            // The length prefix can only be written once all elements are
+            // known, so it's either back-patched or the elements are buffered
+            let (serializer, pending_length) =
+                PendingLength::start(serializer, "sequence")?;
+
+            Ok(SequenceSerializer {
+                pending_length: Some(pending_length),
+                ..SequenceSerializer::new(TypeName::Sequence, None, serializer)
+            })
         }
+    }
 
-        Ok(SequenceSerializer::new(TypeName::Sequence, serializer))
+    pub fn start_bounded(serializer: Serializer<'w, W>) -> Self {
+        // The length of a bounded type is its maximum, and the bounded value
+        // itself is serialized as a single element
+        SequenceSerializer::new(TypeName::Bounded, Some(1), serializer)
     }
 
-    pub fn start_tuple(serializer: Serializer<'w, W>) -> Self {
-        SequenceSerializer::new(TypeName::Tuple, serializer)
+    pub fn start_tuple(length: usize, serializer: Serializer<'w, W>) -> Self {
+        SequenceSerializer::new(TypeName::Tuple, Some(length), serializer)
     }
 
     pub fn start_tuple_struct(
         name: &'static str,
+        length: usize,
         serializer: Serializer<'w, W>,
     ) -> Self {
-        SequenceSerializer::new(TypeName::TupleStruct(name), serializer)
+        SequenceSerializer::new(
+            TypeName::TupleStruct(name),
+            Some(length),
+            serializer,
+        )
     }
 
     pub fn start_tuple_variant(
         type_name: &'static str,
         variant_name: &'static str,
+        length: usize,
         serializer: Serializer<'w, W>,
     ) -> Self {
         SequenceSerializer::new(
             TypeName::TupleVariant(type_name, variant_name),
+            Some(length),
             serializer,
         )
     }
@@ -79,6 +117,8 @@ where
         serializer.serialize_u32(length as u32).map_err(|error| {
             SerializationError::Failure {
                 what: format!("sequence length: {}", length),
+                segment: None,
+                offset: None,
                 cause: Box::new(error.into()),
             }
             .into()
@@ -100,13 +140,39 @@ where
     where
         T: ?Sized + Serialize,
     {
-        if let Some(serializer) = self.serializer.take() {
-            let serializer = value
+        let mut pending_length = self.pending_length.take();
+
+        if let Some(buffer) =
+            pending_length.as_mut().and_then(PendingLength::buffer)
+        {
+            let result = match self.serializer {
+                Some(ref serializer) => {
+                    // The buffered elements are written after the length
+                    let offset = serializer.position + 4 + buffer.len() as u64;
+
+                    value
+                        .serialize(serializer.nested(buffer, offset))
+                        .map_err(|error| self.failure(offset, error))
+                }
+                None => bail!(fatal_error(&self.type_name)),
+            };
+
+            result?;
+
+            self.current_index += 1;
+            self.pending_length = pending_length;
+
+            Ok(())
+        } else if let Some(serializer) = self.serializer.take() {
+            let offset = serializer.position;
+            let mut serializer = value
                 .serialize(serializer)
-                .map_err(|error| self.failure(error))?;
+                .map_err(|error| self.failure(offset, error))?;
 
+            serializer.length_bound = None;
             self.current_index += 1;
             self.serializer = Some(serializer);
+            self.pending_length = pending_length;
 
             Ok(())
         } else {
@@ -115,22 +181,50 @@ where
     }
 
     fn common_end(self) -> Result<Serializer<'w, W>> {
-        if let Some(serializer) = self.serializer {
-            Ok(serializer)
-        } else {
-            bail!(fatal_error(&self.type_name))
+        let mut serializer = match self.serializer {
+            Some(serializer) => serializer,
+            None => bail!(fatal_error(&self.type_name)),
+        };
+
+        if let Some(declared) = self.length {
+            ensure!(
+                self.current_index == declared,
+                SerializationError::SequenceLengthMismatch {
+                    type_name: self.type_name.to_string(),
+                    declared,
+                    actual: self.current_index,
+                }
+            );
+        }
+
+        if let Some(pending_length) = self.pending_length {
+            let what = format!("elements of the type {}", self.type_name);
+
+            serializer =
+                pending_length.finish(self.current_index, &what, serializer)?;
         }
+
+        Ok(serializer)
     }
 
-    fn failure<E>(&self, error: E) -> SerializationError
+    fn failure<E>(&self, offset: u64, error: E) -> SerializationError
     where
         E: Into<CompatSerializationError>,
     {
         let index = self.current_index;
         let type_name = &self.type_name;
 
+        // A bounded value is a wrapper, so its single element isn't part of
+        // the path to the values it contains
+        let segment = match type_name {
+            TypeName::Bounded => None,
+            _ => Some(PathSegment::Element(index)),
+        };
+
         SerializationError::Failure {
             what: format!("element {} of the type {}", index, type_name),
+            segment,
+            offset: Some(offset),
             cause: Box::new(error.into()),
         }
     }
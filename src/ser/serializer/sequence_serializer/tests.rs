@@ -1,6 +1,10 @@
 use {
-    super::super::{super::Serializer, tests::*},
+    super::super::{
+        super::{SerializationError, Serializer},
+        tests::*,
+    },
     serde::ser::Serializer as _,
+    std::io::Cursor,
 };
 
 #[test]
@@ -76,3 +80,126 @@ fn serialize_tuple() {
 
     assert_eq!(buffer, expected_bytes);
 }
+
+#[test]
+fn serialize_sequence_with_unknown_length() {
+    let mut buffer = Vec::new();
+
+    let first_element: i32 = -20;
+    let second_element = "hello";
+
+    {
+        use serde::ser::SerializeSeq;
+
+        let mut sequence_serializer =
+            Serializer::new(&mut buffer).serialize_seq(None).unwrap();
+
+        sequence_serializer
+            .serialize_element(&first_element)
+            .unwrap();
+        sequence_serializer
+            .serialize_element(second_element)
+            .unwrap();
+        sequence_serializer.end().unwrap();
+    }
+
+    let mut expected_bytes = bytes_of(2);
+
+    expected_bytes.append(&mut bytes_of(first_element as u32));
+    expected_bytes.append(&mut bytes_of_str(second_element, 3));
+
+    assert_eq!(buffer, expected_bytes);
+}
+
+#[test]
+fn back_patch_sequence_with_unknown_length() {
+    use serde::ser::SerializeSeq;
+
+    let mut cursor = Cursor::new(vec![0xff; 4]);
+
+    cursor.set_position(4);
+
+    {
+        let mut sequence_serializer = Serializer::new(&mut cursor)
+            .with_back_patching()
+            .serialize_seq(None)
+            .unwrap();
+
+        sequence_serializer.serialize_element(&-20i32).unwrap();
+        sequence_serializer.serialize_element("hello").unwrap();
+        sequence_serializer.end().unwrap();
+    }
+
+    let mut expected_bytes = vec![0xff; 4];
+
+    expected_bytes.append(&mut bytes_of(2));
+    expected_bytes.append(&mut bytes_of(-20i32 as u32));
+    expected_bytes.append(&mut bytes_of_str("hello", 3));
+
+    assert_eq!(cursor.position(), expected_bytes.len() as u64);
+    assert_eq!(cursor.into_inner(), expected_bytes);
+}
+
+#[test]
+fn serialize_empty_sequence_with_unknown_length() {
+    let mut buffer = Vec::new();
+
+    {
+        use serde::ser::SerializeSeq;
+
+        let sequence_serializer =
+            Serializer::new(&mut buffer).serialize_seq(None).unwrap();
+
+        sequence_serializer.end().unwrap();
+    }
+
+    assert_eq!(buffer, bytes_of(0));
+}
+
+#[test]
+fn sequence_with_fewer_elements_than_declared() {
+    use serde::ser::SerializeSeq;
+
+    let mut buffer = Vec::new();
+
+    let mut sequence_serializer =
+        Serializer::new(&mut buffer).serialize_seq(Some(2)).unwrap();
+
+    sequence_serializer.serialize_element(&1u32).unwrap();
+
+    let result = sequence_serializer.end().map(|_| ());
+
+    match result.map_err(SerializationError::from) {
+        Err(SerializationError::SequenceLengthMismatch {
+            declared: 2,
+            actual: 1,
+            ..
+        }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn tuple_variant_with_more_fields_than_declared() {
+    use serde::ser::SerializeTupleVariant;
+
+    let mut buffer = Vec::new();
+
+    let mut sequence_serializer = Serializer::new(&mut buffer)
+        .serialize_tuple_variant("Tuple", 0, "Variant", 1)
+        .unwrap();
+
+    sequence_serializer.serialize_field(&1u32).unwrap();
+    sequence_serializer.serialize_field(&2u32).unwrap();
+
+    let result = sequence_serializer.end().map(|_| ());
+
+    match result.map_err(SerializationError::from) {
+        Err(SerializationError::SequenceLengthMismatch {
+            type_name,
+            declared: 1,
+            actual: 2,
+        }) => assert_eq!(type_name, "tuple variant Tuple::Variant"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -1,6 +1,7 @@
 use std::fmt::{Display, Formatter, Result};
 
 pub enum TypeName {
+    Bounded,
     Sequence,
     Tuple,
     TupleStruct(&'static str),
@@ -10,6 +11,7 @@ pub enum TypeName {
 impl Display for TypeName {
     fn fmt(&self, formatter: &mut Formatter) -> Result {
         match *self {
+            TypeName::Bounded => formatter.write_str("bounded value"),
             TypeName::Sequence => formatter.write_str("sequence"),
             TypeName::Tuple => formatter.write_str("tuple"),
             TypeName::TupleStruct(name) => {
@@ -26,6 +28,11 @@ impl Display for TypeName {
 mod tests {
     use super::TypeName;
 
+    #[test]
+    fn bounded() {
+        assert_eq!(TypeName::Bounded.to_string(), "bounded value".to_string());
+    }
+
     #[test]
     fn sequence() {
         assert_eq!(TypeName::Sequence.to_string(), "sequence".to_string());
@@ -4,6 +4,7 @@ use {
         errors::{CompatSerializationError, Result, SerializationError},
         Serializer,
     },
+    crate::PathSegment,
     byteorder::WriteBytesExt,
     serde::ser::{self, Serialize},
 };
@@ -50,8 +51,9 @@ where
         T: ?Sized + Serialize,
     {
         if let Some(serializer) = self.serializer.take() {
+            let offset = Some(serializer.position);
             let serializer = value.serialize(serializer).map_err(|error| {
-                serialization_error(&self.struct_name, key, error)
+                serialization_error(&self.struct_name, key, offset, error)
             })?;
 
             self.serializer = Some(serializer);
@@ -59,11 +61,18 @@ where
             Ok(())
         } else {
             Err(fatal_error(&self.struct_name)).map_err(|error| {
-                serialization_error(&self.struct_name, key, error).into()
+                serialization_error(&self.struct_name, key, None, error).into()
             })
         }
     }
 
+    fn common_skip_field(&mut self, key: &'static str) -> Result<()> {
+        bail!(SerializationError::StructFieldSkipped {
+            name: self.struct_name.to_string(),
+            field: key,
+        })
+    }
+
     fn common_end(self) -> Result<Serializer<'w, W>> {
         if let Some(serializer) = self.serializer {
             Ok(serializer)
@@ -87,6 +96,10 @@ where
         self.common_serialize_field(key, value)
     }
 
+    fn skip_field(&mut self, key: &'static str) -> Result<()> {
+        self.common_skip_field(key)
+    }
+
     fn end(self) -> Result<Serializer<'w, W>> {
         self.common_end()
     }
@@ -106,6 +119,10 @@ where
         self.common_serialize_field(key, value)
     }
 
+    fn skip_field(&mut self, key: &'static str) -> Result<()> {
+        self.common_skip_field(key)
+    }
+
     fn end(self) -> Result<Serializer<'w, W>> {
         self.common_end()
     }
@@ -119,14 +136,22 @@ fn fatal_error(struct_name: &TypeName) -> CompatSerializationError {
 
 fn serialization_error<E>(
     struct_name: &TypeName,
-    field_name: &str,
+    field_name: &'static str,
+    offset: Option<u64>,
     error: E,
 ) -> SerializationError
 where
     E: Into<CompatSerializationError>,
 {
+    let segment = PathSegment::Field {
+        structure: struct_name.to_string(),
+        field: field_name,
+    };
+
     SerializationError::Failure {
         what: format!("struct field {}::{}", struct_name, field_name),
+        segment: Some(segment),
+        offset,
         cause: Box::new(error.into()),
     }
 }
@@ -27,3 +27,24 @@ fn serialize_struct() {
 
     assert_eq!(buffer, expected_bytes);
 }
+
+#[test]
+fn skipped_field_is_rejected() {
+    let mut buffer = Vec::new();
+
+    let mut struct_serializer = Serializer::new(&mut buffer)
+        .serialize_struct("struct", 2)
+        .unwrap();
+
+    struct_serializer.serialize_field("number", &1u32).unwrap();
+
+    let result = struct_serializer.skip_field("string");
+
+    match result.map_err(SerializationError::from) {
+        Err(SerializationError::StructFieldSkipped { name, field }) => {
+            assert_eq!(name, "struct");
+            assert_eq!(field, "string");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -1,4 +1,9 @@
-use {super::Serializer, serde::ser::Serializer as _, std::mem};
+use {
+    super::{super::SerializationError, Serializer},
+    crate::StringEncoding,
+    serde::ser::Serializer as _,
+    std::mem,
+};
 
 pub fn bytes_of(mut value: u32) -> Vec<u8> {
     let mut bytes = Vec::with_capacity(4);
@@ -291,6 +296,84 @@ fn serialize_string_without_padding() {
     assert_eq!(buffer, bytes_of_str(value, 0));
 }
 
+#[test]
+fn serialize_non_ascii_string() {
+    let mut buffer = Vec::new();
+
+    let error = Serializer::new(&mut buffer)
+        .serialize_str("caf\u{e9}")
+        .map(|_| ())
+        .unwrap_err();
+
+    match SerializationError::from(error) {
+        SerializationError::StringIsNotAscii { string } => {
+            assert_eq!(string, "caf\u{e9}");
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn serialize_utf8_string() {
+    let mut buffer = Vec::new();
+    let value = "caf\u{e9}";
+
+    Serializer::new(&mut buffer)
+        .with_string_encoding(StringEncoding::Utf8)
+        .serialize_str(value)
+        .unwrap();
+
+    assert_eq!(buffer, bytes_of_str(value, 3));
+}
+
+#[test]
+fn serialize_latin1_string() {
+    let mut buffer = Vec::new();
+
+    Serializer::new(&mut buffer)
+        .with_string_encoding(StringEncoding::Latin1)
+        .serialize_str("caf\u{e9}")
+        .unwrap();
+
+    assert_eq!(buffer, bytes_of_opaque(&[b'c', b'a', b'f', 0xe9], 0));
+}
+
+#[test]
+fn serialize_string_outside_of_latin1() {
+    let mut buffer = Vec::new();
+
+    let error = Serializer::new(&mut buffer)
+        .with_string_encoding(StringEncoding::Latin1)
+        .serialize_str("\u{20ac}")
+        .map(|_| ())
+        .unwrap_err();
+
+    match SerializationError::from(error) {
+        SerializationError::StringIsNotLatin1 { string } => {
+            assert_eq!(string, "\u{20ac}");
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn serialize_non_ascii_char_with_ascii_encoding() {
+    let mut buffer = Vec::new();
+
+    let error = Serializer::new(&mut buffer)
+        .with_string_encoding(StringEncoding::Ascii)
+        .serialize_char('\u{e9}')
+        .map(|_| ())
+        .unwrap_err();
+
+    match SerializationError::from(error) {
+        SerializationError::CharIsNotAscii { value } => {
+            assert_eq!(value, '\u{e9}');
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
 #[test]
 fn serialize_void() {
     let mut buffer = Vec::new();
@@ -0,0 +1,26 @@
+use std::io::{self, Write};
+
+/// Writer that discards the data, only counting how many bytes were written.
+#[derive(Default)]
+pub struct SizeCounter {
+    size: u64,
+}
+
+impl SizeCounter {
+    /// Retrieve the number of bytes written so far.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl Write for SizeCounter {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        self.size += buffer.len() as u64;
+
+        Ok(buffer.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use std::io::{self, Seek, SeekFrom, Write};
+
+/// Writer into a borrowed slice of bytes, which fails once the slice is full.
+pub struct SliceWriter<'b> {
+    buffer: &'b mut [u8],
+    position: usize,
+    end: usize,
+    overflowed: bool,
+}
+
+impl<'b> SliceWriter<'b> {
+    /// Create a writer that starts writing at the start of the slice.
+    pub fn new(buffer: &'b mut [u8]) -> Self {
+        SliceWriter {
+            buffer,
+            position: 0,
+            end: 0,
+            overflowed: false,
+        }
+    }
+
+    /// Retrieve the number of bytes written into the slice.
+    pub fn written(&self) -> usize {
+        self.end
+    }
+
+    /// Check if a write failed because it didn't fit in the slice.
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+}
+
+impl Write for SliceWriter<'_> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        let available = self.buffer.len() - self.position;
+
+        if bytes.len() > available {
+            self.overflowed = true;
+
+            return Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "buffer is too small",
+            ));
+        }
+
+        let end = self.position + bytes.len();
+
+        self.buffer[self.position..end].copy_from_slice(bytes);
+        self.position = end;
+        self.end = self.end.max(end);
+
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for SliceWriter<'_> {
+    fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+        let position = match position {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => {
+                (self.position as u64).checked_add_signed(offset)
+            }
+            SeekFrom::End(offset) => {
+                (self.buffer.len() as u64).checked_add_signed(offset)
+            }
+        };
+
+        match position {
+            Some(position) if position <= self.buffer.len() as u64 => {
+                self.position = position as usize;
+
+                Ok(position)
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "seek outside of the buffer",
+            )),
+        }
+    }
+}
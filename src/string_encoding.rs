@@ -0,0 +1,24 @@
+/// How strings and characters are encoded.
+///
+/// XDR strings are defined as sequences of ASCII bytes, but in practice other
+/// encodings are often used. The encoding determines which strings and
+/// characters can be serialized, and how the deserialized bytes are
+/// interpreted.
+///
+/// If no encoding is configured, strings must be ASCII to be serialized but
+/// any UTF-8 string is accepted when deserializing, and characters aren't
+/// restricted.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StringEncoding {
+    /// Only ASCII strings and characters are accepted.
+    Ascii,
+
+    /// Strings are encoded as UTF-8, and any character is accepted.
+    Utf8,
+
+    /// Strings are encoded as ISO 8859-1, where each byte represents the
+    /// character with the same code point. Any sequence of bytes can be
+    /// deserialized, but only strings and characters with code points up to
+    /// `U+00FF` can be serialized.
+    Latin1,
+}
@@ -0,0 +1,186 @@
+use {
+    crate::{
+        bounded::VarArray, from_bytes, to_bytes, DeserializationError,
+        MapEncoding, Options, PathSegment, SerializationError, StringEncoding,
+    },
+    std::{collections::BTreeMap, io::Cursor},
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Entry {
+    name: String,
+    size: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Directory {
+    name: String,
+    entries: Vec<Entry>,
+}
+
+fn directory_with_name(name: &str) -> Directory {
+    Directory {
+        name: "root".to_string(),
+        entries: vec![
+            Entry {
+                name: "a".to_string(),
+                size: 1,
+            },
+            Entry {
+                name: name.to_string(),
+                size: 2,
+            },
+        ],
+    }
+}
+
+fn expected_segments() -> Vec<PathSegment> {
+    vec![
+        PathSegment::Field {
+            structure: "Directory".to_string(),
+            field: "entries",
+        },
+        PathSegment::Element(1),
+        PathSegment::Field {
+            structure: "Entry".to_string(),
+            field: "name",
+        },
+    ]
+}
+
+#[test]
+fn deserialization_error_location() {
+    let bytes = Options::new()
+        .with_string_encoding(StringEncoding::Utf8)
+        .serialize(&directory_with_name("\u{e9}"))
+        .unwrap();
+
+    let error = Options::new()
+        .with_string_encoding(StringEncoding::Ascii)
+        .deserialize::<Directory>(&bytes)
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "deserialized a string that is not ASCII: [195, 169] at \
+         Directory.entries[1].name (byte offset 24)"
+    );
+
+    let error = DeserializationError::from(error);
+
+    assert_eq!(error.path().segments(), &expected_segments()[..]);
+    assert_eq!(error.path().to_string(), "Directory.entries[1].name");
+    assert_eq!(error.offset(), Some(24));
+
+    match error.root_cause() {
+        DeserializationError::InvalidAsciiString { bytes } => {
+            assert_eq!(bytes, &[0xc3, 0xa9]);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn deserialization_error_offset_is_relative_to_the_deserializer() {
+    let mut bytes = vec![0xff; 4];
+
+    Options::new()
+        .with_string_encoding(StringEncoding::Utf8)
+        .to_writer(&mut bytes, &directory_with_name("\u{e9}"))
+        .unwrap();
+
+    let mut reader = Cursor::new(&bytes);
+
+    reader.set_position(4);
+
+    let error = Options::new()
+        .with_string_encoding(StringEncoding::Ascii)
+        .from_reader::<_, Directory>(&mut reader)
+        .unwrap_err();
+
+    assert_eq!(DeserializationError::from(error).offset(), Some(24));
+}
+
+#[test]
+fn serialization_error_location() {
+    let error = to_bytes(&directory_with_name("\u{e9}")).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "string is not ASCII encoded: \u{e9} at Directory.entries[1].name \
+         (byte offset 24)"
+    );
+
+    let error = SerializationError::from(error);
+
+    assert_eq!(error.path().segments(), &expected_segments()[..]);
+    assert_eq!(error.offset(), Some(24));
+
+    match error.root_cause() {
+        SerializationError::StringIsNotAscii { string } => {
+            assert_eq!(string, "\u{e9}");
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn error_location_in_map() {
+    let mut map = BTreeMap::new();
+
+    map.insert(1u32, "a".to_string());
+    map.insert(2u32, "\u{e9}".to_string());
+
+    let options = Options::new().with_map_encoding(MapEncoding::Unsorted);
+    let error = options.serialize(&map).unwrap_err();
+    let error = SerializationError::from(error);
+
+    assert_eq!(error.path().to_string(), "[1].value");
+    assert_eq!(error.offset(), Some(20));
+
+    let bytes = options
+        .with_string_encoding(StringEncoding::Utf8)
+        .serialize(&map)
+        .unwrap();
+    let error = options
+        .with_string_encoding(StringEncoding::Ascii)
+        .deserialize::<BTreeMap<u32, String>>(&bytes)
+        .unwrap_err();
+    let error = DeserializationError::from(error);
+
+    assert_eq!(error.path().segments(), &[PathSegment::Value(1)]);
+    assert_eq!(error.offset(), Some(20));
+}
+
+#[test]
+fn bounded_value_is_not_part_of_the_path() {
+    let names: VarArray<String, 4> =
+        vec!["a".to_string(), "\u{e9}".to_string()].into();
+
+    let error = SerializationError::from(to_bytes(&names).unwrap_err());
+
+    assert_eq!(error.path().segments(), &[PathSegment::Element(1)]);
+    assert_eq!(error.offset(), Some(12));
+
+    let bytes = Options::new()
+        .with_string_encoding(StringEncoding::Utf8)
+        .serialize(&names)
+        .unwrap();
+    let error = Options::new()
+        .with_string_encoding(StringEncoding::Ascii)
+        .deserialize::<VarArray<String, 4>>(&bytes)
+        .unwrap_err();
+    let error = DeserializationError::from(error);
+
+    assert_eq!(error.path().segments(), &[PathSegment::Element(1)]);
+    assert_eq!(error.offset(), Some(12));
+}
+
+#[test]
+fn error_outside_of_a_structure_has_no_location() {
+    let error = from_bytes::<_, bool>(&[0x00, 0x00, 0x00, 0x02]).unwrap_err();
+    let error = DeserializationError::from(error);
+
+    assert!(error.path().is_empty());
+    assert_eq!(error.offset(), None);
+}
@@ -0,0 +1,244 @@
+use {
+    super::{Entry, File, FileType},
+    crate::{
+        from_bytes, from_reader, from_slice, opaque_data::fixed_length,
+        to_bytes, DeserializationError, Deserializer, LimitKind, Limits,
+        Options, SerializationError, Serializer, XdrRead,
+    },
+    serde::{
+        de::{self, IgnoredAny, SeqAccess, Visitor},
+        ser::{SerializeSeq as _, Serializer as _},
+        Deserialize, Deserializer as _,
+    },
+    std::{
+        borrow::Cow,
+        collections::HashMap,
+        fmt::{self, Formatter},
+        io::{self, Cursor},
+    },
+};
+
+fn file_bytes() -> Vec<u8> {
+    let file = File {
+        filename: "sillyprog".to_string(),
+        filetype: FileType::Exec("lisp".to_string()),
+        owner: "john".to_string(),
+        data: "(quit)".as_bytes().into(),
+    };
+
+    to_bytes(&file).unwrap()
+}
+
+#[test]
+fn truncated_input_fails_to_deserialize() {
+    let bytes = file_bytes();
+
+    for length in 0..bytes.len() {
+        let truncated = &bytes[..length];
+
+        assert!(from_bytes::<_, File>(truncated).is_err());
+        assert!(from_slice::<File>(truncated).is_err());
+    }
+}
+
+#[test]
+fn corrupted_input_does_not_panic() {
+    let bytes = file_bytes();
+
+    for index in 0..bytes.len() {
+        for &value in &[0x00, 0x01, 0x7f, 0x80, 0xff] {
+            let mut corrupted = bytes.clone();
+
+            corrupted[index] = value;
+
+            let _ = from_bytes::<_, File>(&corrupted);
+            let _ = from_slice::<File>(&corrupted);
+        }
+    }
+}
+
+#[test]
+fn oversized_length_fails_without_allocating_it() {
+    let bytes = [0xff, 0xff, 0xff, 0xfc, b'a', b'b', b'c', b'd'];
+
+    let error =
+        from_reader::<_, String>(&mut Cursor::new(&bytes[..])).unwrap_err();
+
+    match DeserializationError::from(error).root_cause() {
+        DeserializationError::IoError { cause, .. } => {
+            assert_eq!(cause.kind(), io::ErrorKind::UnexpectedEof);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+
+    assert!(from_bytes::<_, Vec<u8>>(&bytes).is_err());
+    assert!(from_bytes::<_, Vec<u32>>(&bytes).is_err());
+}
+
+#[test]
+fn malformed_values_fail_to_deserialize() {
+    let invalid_bool = [0x00, 0x00, 0x00, 0x02];
+    let invalid_option = [0x00, 0x00, 0x00, 0x02];
+    let invalid_variant = [0x00, 0x00, 0x00, 0x03];
+    let invalid_char = [0x00, 0x00, 0xd8, 0x00];
+    let invalid_string = [0x00, 0x00, 0x00, 0x01, 0xff, 0x00, 0x00, 0x00];
+
+    assert!(from_bytes::<_, bool>(&invalid_bool).is_err());
+    assert!(from_bytes::<_, Option<u32>>(&invalid_option).is_err());
+    assert!(from_bytes::<_, FileType>(&invalid_variant).is_err());
+    assert!(from_bytes::<_, char>(&invalid_char).is_err());
+    assert!(from_bytes::<_, String>(&invalid_string).is_err());
+    assert!(from_bytes::<_, HashMap<u32, u32>>(&[0; 4]).is_err());
+}
+
+fn nested_entries(count: usize) -> Vec<u8> {
+    let mut bytes =
+        [0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x01].repeat(count);
+
+    bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x00]);
+
+    bytes
+}
+
+#[test]
+fn nested_input_is_not_limited_by_default() {
+    let bytes = nested_entries(100);
+
+    let entry = from_bytes::<_, Entry>(&bytes).unwrap();
+
+    assert_eq!(to_bytes(&entry).unwrap(), bytes);
+}
+
+#[test]
+fn deeply_nested_input_is_rejected_with_depth_limit() {
+    let bytes = nested_entries(100_000);
+    let options =
+        Options::new().with_limits(Limits::default().with_max_depth(128));
+
+    let errors = vec![
+        options.deserialize::<Entry>(&bytes).unwrap_err(),
+        options
+            .from_reader::<_, Entry>(&mut Cursor::new(&bytes))
+            .unwrap_err(),
+    ];
+
+    for error in errors {
+        match DeserializationError::from(error).root_cause() {
+            DeserializationError::LimitExceeded { limit, maximum, .. } => {
+                assert_eq!(*limit, LimitKind::Depth);
+                assert_eq!(*maximum, 128);
+            }
+            error => panic!("unexpected error: {}", error),
+        }
+    }
+}
+
+#[test]
+fn struct_visitor_requesting_extra_fields() {
+    struct Greedy;
+
+    impl<'de> Deserialize<'de> for Greedy {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_struct("Greedy", &["only"], Greedy)
+        }
+    }
+
+    impl<'de> Visitor<'de> for Greedy {
+        type Value = Greedy;
+
+        fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+            write!(formatter, "a struct with a single field")
+        }
+
+        fn visit_seq<A>(self, mut sequence: A) -> Result<Greedy, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            assert!(sequence.next_element::<u32>()?.is_some());
+            assert!(sequence.next_element::<u32>()?.is_none());
+
+            Ok(Greedy)
+        }
+    }
+
+    from_bytes::<_, Greedy>(&[0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02])
+        .unwrap();
+}
+
+#[test]
+fn fixed_length_opaque_with_unrepresentable_length() {
+    let mut cursor = Cursor::new(vec![0x00; 8]);
+
+    let error = Deserializer::new(&mut cursor)
+        .deserialize_tuple_struct(
+            fixed_length::TYPE_NAME,
+            usize::MAX,
+            IgnoredAny,
+        )
+        .map_err(DeserializationError::from)
+        .unwrap_err();
+
+    match error {
+        DeserializationError::TupleHasTooManyElements { length } => {
+            assert_eq!(length, usize::MAX);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn reader_returning_fewer_bytes_than_requested() {
+    struct ShortReader;
+
+    impl<'de> XdrRead<'de> for ShortReader {
+        fn read_into(&mut self, buffer: &mut [u8]) -> io::Result<()> {
+            buffer.copy_from_slice(&[0x00, 0x00, 0x00, 0x08][..buffer.len()]);
+
+            Ok(())
+        }
+
+        fn read_bytes(&mut self, _: usize) -> io::Result<Cow<'de, [u8]>> {
+            Ok(Cow::Owned(vec![b'a'; 2]))
+        }
+
+        fn trailing_bytes(&mut self) -> io::Result<u64> {
+            Ok(0)
+        }
+    }
+
+    let error = String::deserialize(&mut Deserializer::new(&mut ShortReader))
+        .unwrap_err();
+
+    match DeserializationError::from(error).root_cause() {
+        DeserializationError::IoError { cause, .. } => {
+            assert_eq!(cause.kind(), io::ErrorKind::UnexpectedEof);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn invalid_serializer_usage_fails() {
+    let mut buffer = Vec::new();
+
+    let mut map = HashMap::new();
+
+    map.insert(1u32, 2u32);
+
+    assert!(to_bytes(&map).is_err());
+    assert!(to_bytes(&"caf\u{e9}").is_err());
+
+    let sequence_serializer =
+        Serializer::new(&mut buffer).serialize_seq(Some(1)).unwrap();
+
+    match sequence_serializer.end().map(|_| ()) {
+        Err(error) => match SerializationError::from(error) {
+            SerializationError::SequenceLengthMismatch { .. } => {}
+            error => panic!("unexpected error: {}", error),
+        },
+        Ok(()) => panic!("unexpected success"),
+    }
+}
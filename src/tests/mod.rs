@@ -0,0 +1,535 @@
+mod error_location;
+mod malformed_input;
+
+use {
+    super::{
+        from_bytes, from_bytes_exact, from_bytes_strict, from_reader,
+        from_slice, serialized_size, take_from_bytes, to_bytes, to_slice,
+        to_writer, DeserializationError, Deserializer, LimitKind, Limits,
+        MapEncoding, Options, SerializationError, Serializer, SliceReader,
+    },
+    serde::{Deserialize, Serialize},
+    serde_bytes,
+    std::{collections::HashMap, io::Cursor},
+};
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+enum FileType {
+    Text,
+    Data(String),
+    Exec(String),
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct File {
+    filename: String,
+    filetype: FileType,
+    owner: String,
+    #[serde(with = "serde_bytes")]
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct BorrowedFile<'a> {
+    filename: &'a str,
+    filetype: FileType,
+    owner: &'a str,
+    #[serde(borrow)]
+    data: serde_bytes::Bytes<'a>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct Entry {
+    value: u32,
+    next: Option<Box<Entry>>,
+}
+
+#[test]
+fn serialization_functions_are_equivalent() {
+    let file_contents: Vec<u8> = "(quit)".as_bytes().into();
+
+    let file = File {
+        filename: "sillyprog".to_string(),
+        filetype: FileType::Exec("lisp".to_string()),
+        owner: "john".to_string(),
+        data: file_contents,
+    };
+
+    let serialized_to_bytes = to_bytes(&file).unwrap();
+    let mut serialized_to_writer = Vec::new();
+
+    to_writer(&mut serialized_to_writer, &file).unwrap();
+
+    assert_eq!(serialized_to_bytes, serialized_to_writer);
+}
+
+#[test]
+fn serialized_bytes() {
+    let file_contents: Vec<u8> = "(quit)".as_bytes().into();
+
+    let file = File {
+        filename: "sillyprog".to_string(),
+        filetype: FileType::Exec("lisp".to_string()),
+        owner: "john".to_string(),
+        data: file_contents,
+    };
+
+    let bytes = to_bytes(&file).unwrap();
+
+    let expected_bytes = vec![
+        0x00, 0x00, 0x00, 0x09, 's' as u8, 'i' as u8, 'l' as u8, 'l' as u8,
+        'y' as u8, 'p' as u8, 'r' as u8, 'o' as u8, 'g' as u8, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x04, 'l' as u8,
+        'i' as u8, 's' as u8, 'p' as u8, 0x00, 0x00, 0x00, 0x04, 'j' as u8,
+        'o' as u8, 'h' as u8, 'n' as u8, 0x00, 0x00, 0x00, 0x06, '(' as u8,
+        'q' as u8, 'u' as u8, 'i' as u8, 't' as u8, ')' as u8, 0x00, 0x00,
+    ];
+
+    assert_eq!(bytes, expected_bytes);
+}
+
+#[test]
+fn serialize_deserialize() {
+    let file_contents: Vec<u8> = "(quit)".as_bytes().into();
+
+    let initial_file = File {
+        filename: "sillyprog".to_string(),
+        filetype: FileType::Exec("lisp".to_string()),
+        owner: "john".to_string(),
+        data: file_contents,
+    };
+
+    let bytes = to_bytes(&initial_file).unwrap();
+    let length = bytes.len() as u64;
+    let mut cursor = Cursor::new(bytes);
+
+    let recovered_file = from_reader(&mut cursor).unwrap();
+
+    assert_eq!(initial_file, recovered_file);
+    assert_eq!(cursor.position(), length);
+}
+
+#[test]
+fn deserialization_functions_are_equivalent() {
+    let source_bytes = vec![
+        0x00, 0x00, 0x00, 0x09, 's' as u8, 'i' as u8, 'l' as u8, 'l' as u8,
+        'y' as u8, 'p' as u8, 'r' as u8, 'o' as u8, 'g' as u8, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x04, 'l' as u8,
+        'i' as u8, 's' as u8, 'p' as u8, 0x00, 0x00, 0x00, 0x04, 'j' as u8,
+        'o' as u8, 'h' as u8, 'n' as u8, 0x00, 0x00, 0x00, 0x06, '(' as u8,
+        'q' as u8, 'u' as u8, 'i' as u8, 't' as u8, ')' as u8, 0x00, 0x00,
+    ];
+
+    let deserialized_from_bytes: File = from_bytes(&source_bytes).unwrap();
+
+    let mut reader = Cursor::new(source_bytes);
+    let deserialized_from_reader: File = from_reader(&mut reader).unwrap();
+
+    assert_eq!(deserialized_from_bytes, deserialized_from_reader);
+}
+
+#[test]
+fn strict_deserialization_of_canonical_encoding() {
+    let file = File {
+        filename: "sillyprog".to_string(),
+        filetype: FileType::Data("lisp".to_string()),
+        owner: "john".to_string(),
+        data: "(quit)".as_bytes().into(),
+    };
+
+    let bytes = to_bytes(&file).unwrap();
+    let deserialized_file: File = from_bytes_strict(&bytes).unwrap();
+
+    assert_eq!(deserialized_file, file);
+}
+
+#[test]
+fn strict_deserialization_of_non_canonical_encoding() {
+    let mut bytes = to_bytes(&File {
+        filename: "sillyprog".to_string(),
+        filetype: FileType::Text,
+        owner: "john".to_string(),
+        data: "(quit)".as_bytes().into(),
+    })
+    .unwrap();
+
+    let last_padding_byte = bytes.len() - 1;
+
+    bytes[last_padding_byte] = 0xff;
+
+    assert!(from_bytes::<_, File>(&bytes).is_ok());
+    assert!(from_bytes_strict::<_, File>(&bytes).is_err());
+}
+
+#[test]
+fn sorted_map_serialization_is_deterministic() {
+    let owners = ["john", "mary", "alice", "bob"];
+    let mut permissions = HashMap::new();
+
+    for (index, owner) in owners.iter().enumerate() {
+        permissions.insert(owner.to_string(), index as u32);
+    }
+
+    let mut bytes = Vec::new();
+
+    permissions
+        .serialize(
+            Serializer::new(&mut bytes).with_map_encoding(MapEncoding::Sorted),
+        )
+        .unwrap();
+
+    let mut reordered_permissions = HashMap::new();
+
+    for (index, owner) in owners.iter().enumerate().rev() {
+        reordered_permissions.insert(owner.to_string(), index as u32);
+    }
+
+    let mut reordered_bytes = Vec::new();
+
+    reordered_permissions
+        .serialize(
+            Serializer::new(&mut reordered_bytes)
+                .with_map_encoding(MapEncoding::Sorted),
+        )
+        .unwrap();
+
+    assert_eq!(bytes, reordered_bytes);
+
+    let mut cursor = Cursor::new(bytes);
+    let mut deserializer =
+        Deserializer::new(&mut cursor).with_map_encoding(MapEncoding::Sorted);
+
+    let deserialized_permissions =
+        HashMap::<String, u32>::deserialize(&mut deserializer).unwrap();
+
+    assert_eq!(deserialized_permissions, permissions);
+}
+
+#[test]
+fn deserialization_from_slice_borrows_from_input() {
+    let file = File {
+        filename: "sillyprog".to_string(),
+        filetype: FileType::Text,
+        owner: "john".to_string(),
+        data: b"(quit)".to_vec(),
+    };
+
+    let bytes = to_bytes(&file).unwrap();
+    let input = bytes.as_ptr_range();
+
+    let borrowed_file: BorrowedFile = from_slice(&bytes).unwrap();
+
+    assert_eq!(borrowed_file.filename, "sillyprog");
+    assert_eq!(borrowed_file.owner, "john");
+    assert_eq!(&borrowed_file.data[..], b"(quit)");
+    assert!(input.contains(&borrowed_file.filename.as_ptr()));
+    assert!(input.contains(&borrowed_file.owner.as_ptr()));
+    assert!(input.contains(&borrowed_file.data.as_ptr()));
+}
+
+#[test]
+fn deserialization_from_slice_of_owned_data() {
+    let file = File {
+        filename: "sillyprog".to_string(),
+        filetype: FileType::Exec("lisp".to_string()),
+        owner: "john".to_string(),
+        data: b"(quit)".to_vec(),
+    };
+
+    let bytes = to_bytes(&file).unwrap();
+    let deserialized_file: File = from_slice(&bytes).unwrap();
+
+    assert_eq!(deserialized_file, file);
+}
+
+#[test]
+fn nesting_within_depth_limit_is_accepted() {
+    let entry = Entry {
+        value: 1,
+        next: Some(Box::new(Entry {
+            value: 2,
+            next: None,
+        })),
+    };
+
+    let bytes = to_bytes(&entry).unwrap();
+    let mut cursor = Cursor::new(bytes);
+    let limits = Limits::default().with_max_depth(4);
+    let mut deserializer = Deserializer::new(&mut cursor).with_limits(limits);
+
+    let deserialized_entry = Entry::deserialize(&mut deserializer).unwrap();
+
+    assert_eq!(deserialized_entry, entry);
+}
+
+#[test]
+fn nesting_beyond_depth_limit_is_rejected() {
+    let mut bytes = Vec::new();
+
+    for value in 0..100_000u32 {
+        bytes.extend_from_slice(&value.to_be_bytes());
+        bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x01]);
+    }
+
+    let mut cursor = Cursor::new(bytes);
+    let limits = Limits::default().with_max_depth(64);
+    let mut deserializer = Deserializer::new(&mut cursor).with_limits(limits);
+
+    let error = Entry::deserialize(&mut deserializer).unwrap_err();
+
+    match DeserializationError::from(error).root_cause() {
+        DeserializationError::LimitExceeded {
+            limit,
+            requested,
+            maximum,
+        } => {
+            assert_eq!(*limit, LimitKind::Depth);
+            assert_eq!(*requested, 65);
+            assert_eq!(*maximum, 64);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn exact_deserialization_of_whole_input() {
+    let bytes = to_bytes(&(1u32, "two".to_string())).unwrap();
+
+    let value: (u32, String) = from_bytes_exact(&bytes).unwrap();
+
+    assert_eq!(value, (1, "two".to_string()));
+}
+
+#[test]
+fn exact_deserialization_rejects_trailing_bytes() {
+    let mut bytes = to_bytes(&7u32).unwrap();
+
+    bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x08, 0x09]);
+
+    let error = from_bytes_exact::<_, u32>(&bytes).unwrap_err();
+
+    match DeserializationError::from(error) {
+        DeserializationError::TrailingBytes { remaining } => {
+            assert_eq!(remaining, 5)
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn end_of_deserializer_reads_at_most_one_trailing_byte() {
+    let mut cursor = Cursor::new(vec![0x00, 0x00, 0x00, 0x01, 0xff, 0xff]);
+    let mut deserializer = Deserializer::new(&mut cursor);
+
+    let value = u32::deserialize(&mut deserializer).unwrap();
+    let error = deserializer.end().unwrap_err();
+
+    assert_eq!(value, 1);
+    assert_eq!(cursor.position(), 5);
+
+    match DeserializationError::from(error) {
+        DeserializationError::TrailingBytes { remaining } => {
+            assert_eq!(remaining, 1)
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn end_of_slice_reader_counts_trailing_bytes() {
+    let bytes = [0x00, 0x00, 0x00, 0x01, 0xff, 0xff, 0xff];
+    let mut reader = SliceReader::new(&bytes);
+    let mut deserializer = Deserializer::new(&mut reader);
+
+    let value = u32::deserialize(&mut deserializer).unwrap();
+    let error = deserializer.end().unwrap_err();
+
+    assert_eq!(value, 1);
+    assert_eq!(reader.position(), 4);
+
+    match DeserializationError::from(error) {
+        DeserializationError::TrailingBytes { remaining } => {
+            assert_eq!(remaining, 3)
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn take_from_bytes_returns_the_unconsumed_tail() {
+    let mut bytes = to_bytes(&"head").unwrap();
+
+    bytes.extend_from_slice(&[0x01, 0x02, 0x03]);
+
+    let (value, tail): (&str, &[u8]) = take_from_bytes(&bytes).unwrap();
+
+    assert_eq!(value, "head");
+    assert_eq!(tail, &[0x01, 0x02, 0x03]);
+}
+
+#[test]
+fn options_are_applied_to_each_call() {
+    let options = Options::new().with_map_encoding(MapEncoding::Sorted);
+    let mut map = HashMap::new();
+
+    map.insert(2u32, "two".to_string());
+    map.insert(1u32, "one".to_string());
+
+    let bytes = options.serialize(&map).unwrap();
+    let mut serialized_to_writer = Vec::new();
+
+    options.to_writer(&mut serialized_to_writer, &map).unwrap();
+
+    let deserialized_map: HashMap<u32, String> =
+        options.deserialize(&bytes).unwrap();
+    let read_map: HashMap<u32, String> =
+        options.from_reader(&mut Cursor::new(&bytes)).unwrap();
+
+    assert_eq!(serialized_to_writer, bytes);
+    assert_eq!(deserialized_map, map);
+    assert_eq!(read_map, map);
+}
+
+#[test]
+fn options_configure_deserialization() {
+    let bytes = vec![0x00, 0x00, 0x00, 0x02, b'H', b'i', 0x00, 0x01];
+
+    let lenient: String = from_bytes(&bytes).unwrap();
+    let strict_error = Options::new()
+        .with_strict_mode(true)
+        .deserialize::<String>(&bytes)
+        .unwrap_err();
+    let limited_error = Options::new()
+        .with_limits(Limits::default().with_max_length(1))
+        .from_reader::<_, String>(&mut Cursor::new(&bytes))
+        .unwrap_err();
+
+    assert_eq!(lenient, "Hi");
+
+    match DeserializationError::from(strict_error) {
+        DeserializationError::NonZeroPadding { .. } => {}
+        error => panic!("unexpected error: {}", error),
+    }
+
+    match DeserializationError::from(limited_error) {
+        DeserializationError::LimitExceeded { limit, .. } => {
+            assert_eq!(limit, LimitKind::Length)
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn serialized_size_matches_serialized_bytes() {
+    let file = File {
+        filename: "sillyprog".to_string(),
+        filetype: FileType::Exec("lisp".to_string()),
+        owner: "john".to_string(),
+        data: "(quit)".as_bytes().into(),
+    };
+
+    let size = serialized_size(&file).unwrap();
+    let bytes = to_bytes(&file).unwrap();
+
+    assert_eq!(size, 48);
+    assert_eq!(bytes.len() as u64, size);
+}
+
+#[test]
+fn serialized_size_of_sorted_map() {
+    let mut map = HashMap::new();
+
+    map.insert("b".to_string(), 2u32);
+    map.insert("a".to_string(), 1u32);
+
+    let options = Options::new().with_map_encoding(MapEncoding::Sorted);
+    let bytes = options.serialize(&map).unwrap();
+
+    assert_eq!(options.serialized_size(&map).unwrap(), bytes.len() as u64);
+}
+
+#[test]
+fn serialized_size_fails_like_serialization() {
+    assert!(serialized_size(&"caf\u{e9}").is_err());
+}
+
+#[test]
+fn serialization_into_slice() {
+    let mut buffer = [0xff; 16];
+
+    let size = to_slice(&(1u32, "ab"), &mut buffer).unwrap();
+
+    assert_eq!(size, 12);
+    assert_eq!(buffer[..size], to_bytes(&(1u32, "ab")).unwrap()[..]);
+    assert_eq!(buffer[size..], [0xff; 4]);
+}
+
+#[test]
+fn serialization_into_slice_that_is_too_small() {
+    let mut buffer = [0xff; 8];
+
+    let error = to_slice(&(1u32, "ab"), &mut buffer).unwrap_err();
+
+    match SerializationError::from(error) {
+        SerializationError::BufferTooSmall {
+            required,
+            available,
+        } => {
+            assert_eq!(required, 12);
+            assert_eq!(available, 8);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn serialization_of_sequence_with_unknown_length() {
+    struct EvenNumbers(Vec<u32>);
+
+    impl Serialize for EvenNumbers {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.collect_seq(self.0.iter().filter(|&x| x % 2 == 0))
+        }
+    }
+
+    let numbers = EvenNumbers(vec![1, 2, 3, 4, 5, 6]);
+
+    let bytes = to_bytes(&numbers).unwrap();
+    let deserialized: Vec<u32> = from_bytes(&bytes).unwrap();
+
+    assert_eq!(deserialized, vec![2, 4, 6]);
+    assert_eq!(serialized_size(&numbers).unwrap(), bytes.len() as u64);
+
+    let mut buffer = [0xff; 20];
+    let size = to_slice(&numbers, &mut buffer).unwrap();
+
+    assert_eq!(buffer[..size], bytes[..]);
+}
+
+#[test]
+fn serialization_of_struct_with_skipped_field() {
+    #[derive(Serialize)]
+    struct Entry {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        comment: Option<String>,
+        value: u32,
+    }
+
+    let entry = Entry {
+        comment: None,
+        value: 1,
+    };
+
+    let error = to_bytes(&entry).unwrap_err();
+
+    match SerializationError::from(error) {
+        SerializationError::StructFieldSkipped { name, field } => {
+            assert_eq!(name, "Entry");
+            assert_eq!(field, "comment");
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
@@ -0,0 +1,358 @@
+use {
+    serde::{
+        de::{
+            self, value, DeserializeSeed, EnumAccess, IntoDeserializer,
+            VariantAccess, Visitor,
+        },
+        forward_to_deserialize_any,
+        ser::{self, Error as _, Impossible, Serialize},
+    },
+    std::marker::PhantomData,
+};
+
+/// A discriminant that was captured by the default arm of a union.
+///
+/// Discriminants are deserialized before the arm is known, so the value is
+/// recorded in order to be deserialized again as the first field of the
+/// default arm. XDR discriminants are integers, booleans or enumerations, so
+/// only those kinds of values can be captured.
+#[derive(Clone, PartialEq)]
+pub enum Captured {
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    NewtypeStruct(Box<Captured>),
+    UnitVariant(u32),
+}
+
+impl Captured {
+    /// Capture a discriminant by serializing it.
+    pub fn capture<T, E>(discriminant: &T) -> Result<Self, E>
+    where
+        T: ?Sized + Serialize,
+        E: de::Error,
+    {
+        discriminant.serialize(CaptureSerializer).map_err(E::custom)
+    }
+
+    /// Create a deserializer that replays the captured discriminant.
+    pub fn replay<E>(self) -> CapturedDeserializer<E> {
+        CapturedDeserializer {
+            captured: self,
+            _error: PhantomData,
+        }
+    }
+}
+
+/// Serializer that records a discriminant into a `Captured` value.
+struct CaptureSerializer;
+
+impl CaptureSerializer {
+    fn unsupported<O>() -> Result<O, value::Error> {
+        Err(value::Error::custom(
+            "the discriminant of a default union arm must be an integer, a \
+             boolean or an enumeration",
+        ))
+    }
+}
+
+impl ser::Serializer for CaptureSerializer {
+    type Ok = Captured;
+    type Error = value::Error;
+
+    type SerializeSeq = Impossible<Captured, value::Error>;
+    type SerializeTuple = Impossible<Captured, value::Error>;
+    type SerializeTupleStruct = Impossible<Captured, value::Error>;
+    type SerializeTupleVariant = Impossible<Captured, value::Error>;
+    type SerializeMap = Impossible<Captured, value::Error>;
+    type SerializeStruct = Impossible<Captured, value::Error>;
+    type SerializeStructVariant = Impossible<Captured, value::Error>;
+
+    fn serialize_bool(self, value: bool) -> Result<Captured, value::Error> {
+        Ok(Captured::Bool(value))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Captured, value::Error> {
+        Ok(Captured::I8(value))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Captured, value::Error> {
+        Ok(Captured::I16(value))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Captured, value::Error> {
+        Ok(Captured::I32(value))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<Captured, value::Error> {
+        Ok(Captured::I64(value))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Captured, value::Error> {
+        Ok(Captured::U8(value))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Captured, value::Error> {
+        Ok(Captured::U16(value))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Captured, value::Error> {
+        Ok(Captured::U32(value))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Captured, value::Error> {
+        Ok(Captured::U64(value))
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<Captured, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<Captured, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_char(self, _: char) -> Result<Captured, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_str(self, _: &str) -> Result<Captured, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<Captured, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_none(self) -> Result<Captured, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_some<V>(self, _: &V) -> Result<Captured, value::Error>
+    where
+        V: ?Sized + Serialize,
+    {
+        Self::unsupported()
+    }
+
+    fn serialize_unit(self) -> Result<Captured, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_unit_struct(
+        self,
+        _: &'static str,
+    ) -> Result<Captured, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        _: &'static str,
+    ) -> Result<Captured, value::Error> {
+        Ok(Captured::UnitVariant(variant_index))
+    }
+
+    fn serialize_newtype_struct<V>(
+        self,
+        _: &'static str,
+        value: &V,
+    ) -> Result<Captured, value::Error>
+    where
+        V: ?Sized + Serialize,
+    {
+        let captured = value.serialize(self)?;
+
+        Ok(Captured::NewtypeStruct(Box::new(captured)))
+    }
+
+    fn serialize_newtype_variant<V>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &V,
+    ) -> Result<Captured, value::Error>
+    where
+        V: ?Sized + Serialize,
+    {
+        Self::unsupported()
+    }
+
+    fn serialize_seq(
+        self,
+        _: Option<usize>,
+    ) -> Result<Self::SerializeSeq, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_tuple(
+        self,
+        _: usize,
+    ) -> Result<Self::SerializeTuple, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_map(
+        self,
+        _: Option<usize>,
+    ) -> Result<Self::SerializeMap, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, value::Error> {
+        Self::unsupported()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, value::Error> {
+        Self::unsupported()
+    }
+}
+
+/// Deserializer that replays a captured discriminant.
+pub struct CapturedDeserializer<E> {
+    captured: Captured,
+    _error: PhantomData<E>,
+}
+
+impl<'de, E> de::Deserializer<'de> for CapturedDeserializer<E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.captured {
+            Captured::Bool(value) => visitor.visit_bool(value),
+            Captured::I8(value) => visitor.visit_i8(value),
+            Captured::I16(value) => visitor.visit_i16(value),
+            Captured::I32(value) => visitor.visit_i32(value),
+            Captured::I64(value) => visitor.visit_i64(value),
+            Captured::U8(value) => visitor.visit_u8(value),
+            Captured::U16(value) => visitor.visit_u16(value),
+            Captured::U32(value) => visitor.visit_u32(value),
+            Captured::U64(value) => visitor.visit_u64(value),
+            Captured::NewtypeStruct(captured) => {
+                visitor.visit_newtype_struct(captured.replay())
+            }
+            Captured::UnitVariant(index) => {
+                visitor.visit_enum(UnitVariantDeserializer {
+                    index,
+                    _error: PhantomData::<E>,
+                })
+            }
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct UnitVariantDeserializer<E> {
+    index: u32,
+    _error: PhantomData<E>,
+}
+
+impl<'de, E> EnumAccess<'de> for UnitVariantDeserializer<E>
+where
+    E: de::Error,
+{
+    type Error = E;
+    type Variant = Self;
+
+    fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self), E>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let value = seed.deserialize(self.index.into_deserializer())?;
+
+        Ok((value, self))
+    }
+}
+
+impl<'de, E> VariantAccess<'de> for UnitVariantDeserializer<E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn unit_variant(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<S>(self, _: S) -> Result<S::Value, E>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        Err(E::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"newtype variant",
+        ))
+    }
+
+    fn tuple_variant<V>(self, _: usize, _: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        Err(E::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"tuple variant",
+        ))
+    }
+
+    fn struct_variant<V>(
+        self,
+        _: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        Err(E::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"struct variant",
+        ))
+    }
+}
@@ -0,0 +1,334 @@
+use {
+    super::{captured::Captured, variant_of, Union},
+    serde::{
+        de::{
+            self, DeserializeSeed, EnumAccess, Error, IntoDeserializer,
+            SeqAccess, Unexpected, VariantAccess, Visitor,
+        },
+        forward_to_deserialize_any,
+    },
+    std::{
+        fmt::{self, Formatter},
+        marker::PhantomData,
+    },
+};
+
+/// Deserializer that reads an explicit discriminant and maps it to the index
+/// of the respective enum variant.
+pub struct UnionDeserializer<T, D> {
+    deserializer: D,
+    _union: PhantomData<T>,
+}
+
+impl<T, D> UnionDeserializer<T, D> {
+    pub fn new(deserializer: D) -> Self {
+        UnionDeserializer {
+            deserializer,
+            _union: PhantomData,
+        }
+    }
+}
+
+impl<'de, T, D> de::Deserializer<'de> for UnionDeserializer<T, D>
+where
+    T: Union,
+    D: de::Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn deserialize_any<V>(self, _: V) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(D::Error::custom("only enums can be deserialized as unions"))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserializer.deserialize_tuple(
+            2,
+            UnionVisitor {
+                name,
+                variants,
+                visitor,
+                _union: PhantomData::<T>,
+            },
+        )
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct UnionVisitor<T, V> {
+    name: &'static str,
+    variants: &'static [&'static str],
+    visitor: V,
+    _union: PhantomData<T>,
+}
+
+impl<'de, T, V> Visitor<'de> for UnionVisitor<T, V>
+where
+    T: Union,
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "union {}", self.name)
+    }
+
+    fn visit_seq<A>(self, mut sequence: A) -> Result<V::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let discriminant: T::Discriminant = sequence
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+
+        let index = variant_of::<T>(&discriminant);
+
+        let variant = index.and_then(|index| {
+            self.variants.get(index).map(|&name| (index, name))
+        });
+
+        let (index, variant_name) = variant.ok_or_else(|| {
+            A::Error::invalid_value(
+                Unexpected::Other(&format!("discriminant {:?}", discriminant)),
+                &&*format!("a discriminant of union {}", self.name),
+            )
+        })?;
+
+        let captured_discriminant = if T::DEFAULT_ARM == Some(index) {
+            Some(Captured::capture(&discriminant)?)
+        } else {
+            None
+        };
+
+        self.visitor.visit_enum(ArmDeserializer {
+            index: index as u32,
+            variant_name,
+            captured_discriminant,
+            sequence,
+        })
+    }
+}
+
+/// Deserializer for the arm of a union, which is the element that follows the
+/// discriminant.
+///
+/// If the arm is the default arm, the discriminant is provided to the variant
+/// as its first field.
+struct ArmDeserializer<A> {
+    index: u32,
+    variant_name: &'static str,
+    captured_discriminant: Option<Captured>,
+    sequence: A,
+}
+
+impl<A> ArmDeserializer<A> {
+    fn next_arm<'de, S>(mut self, seed: S) -> Result<S::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+        S: DeserializeSeed<'de>,
+    {
+        self.sequence
+            .next_element_seed(seed)?
+            .ok_or_else(|| A::Error::invalid_length(1, &"a union arm"))
+    }
+}
+
+impl<'de, A> EnumAccess<'de> for ArmDeserializer<A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = Self;
+
+    fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self), A::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let value = seed.deserialize(self.index.into_deserializer())?;
+
+        Ok((value, self))
+    }
+}
+
+impl<'de, A> VariantAccess<'de> for ArmDeserializer<A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, A::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        match self.captured_discriminant {
+            Some(discriminant) => seed.deserialize(discriminant.replay()),
+            None => self.next_arm(seed),
+        }
+    }
+
+    fn tuple_variant<V>(
+        mut self,
+        length: usize,
+        visitor: V,
+    ) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.captured_discriminant.take() {
+            Some(discriminant) => self.next_arm(TupleArm {
+                length: length.saturating_sub(1),
+                visitor: CapturingVisitor {
+                    discriminant,
+                    visitor,
+                },
+            }),
+            None => self.next_arm(TupleArm { length, visitor }),
+        }
+    }
+
+    fn struct_variant<V>(
+        mut self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let name = self.variant_name;
+
+        match self.captured_discriminant.take() {
+            Some(discriminant) => self.next_arm(StructArm {
+                name,
+                fields: fields.get(1..).unwrap_or(&[]),
+                visitor: CapturingVisitor {
+                    discriminant,
+                    visitor,
+                },
+            }),
+            None => self.next_arm(StructArm {
+                name,
+                fields,
+                visitor,
+            }),
+        }
+    }
+}
+
+struct TupleArm<V> {
+    length: usize,
+    visitor: V,
+}
+
+impl<'de, V> DeserializeSeed<'de> for TupleArm<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<V::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(self.length, self.visitor)
+    }
+}
+
+struct StructArm<V> {
+    name: &'static str,
+    fields: &'static [&'static str],
+    visitor: V,
+}
+
+impl<'de, V> DeserializeSeed<'de> for StructArm<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<V::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(self.name, self.fields, self.visitor)
+    }
+}
+
+/// Visitor for the payload of a default arm, which prepends the captured
+/// discriminant to the fields that are deserialized.
+struct CapturingVisitor<V> {
+    discriminant: Captured,
+    visitor: V,
+}
+
+impl<'de, V> Visitor<'de> for CapturingVisitor<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    fn visit_seq<A>(self, sequence: A) -> Result<V::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.visitor.visit_seq(CapturedSequence {
+            discriminant: Some(self.discriminant),
+            sequence,
+        })
+    }
+}
+
+struct CapturedSequence<A> {
+    discriminant: Option<Captured>,
+    sequence: A,
+}
+
+impl<'de, A> SeqAccess<'de> for CapturedSequence<A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<S>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, A::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        match self.discriminant.take() {
+            Some(discriminant) => {
+                seed.deserialize(discriminant.replay()).map(Some)
+            }
+            None => self.sequence.next_element_seed(seed),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        let captured = self.discriminant.as_ref().map_or(0, |_| 1);
+
+        self.sequence.size_hint().map(|length| length + captured)
+    }
+}
@@ -0,0 +1,150 @@
+use {
+    self::{deserializer::UnionDeserializer, serializer::UnionSerializer},
+    serde::{
+        de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer,
+    },
+    std::fmt::Debug,
+};
+
+/// An enum that is serialized as an XDR discriminated union with explicit
+/// discriminant values.
+///
+/// By default, an enum is serialized with the index of its variant as the
+/// discriminant. Implementing this trait allows each variant to have an
+/// arbitrary discriminant, like the `case` values of an XDR union, of any
+/// type that the union switches on.
+/// The enum must then be serialized and deserialized using the functions in
+/// this module, through the `with` serde attribute.
+///
+/// An XDR `default:` arm can be represented by a variant that captures the
+/// discriminant in its first field, and that is marked as the
+/// [`DEFAULT_ARM`](#associatedconstant.DEFAULT_ARM).
+///
+/// # Examples
+///
+/// ```
+/// extern crate serde_xdr;
+/// #[macro_use]
+/// extern crate serde_derive;
+///
+/// use serde_xdr::union::Union;
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// enum Status {
+///     Ok(u32),
+///     NotFound,
+///     Failed(String),
+/// }
+///
+/// impl Union for Status {
+///     type Discriminant = i32;
+///
+///     const DISCRIMINANTS: &'static [i32] = &[0, 10001, -1];
+/// }
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// struct Reply {
+///     #[serde(with = "serde_xdr::union")]
+///     status: Status,
+/// }
+///
+/// fn main() {
+///     let reply = Reply {
+///         status: Status::NotFound,
+///     };
+///
+///     let bytes = serde_xdr::to_bytes(&reply).unwrap();
+///
+///     assert_eq!(bytes, vec![0x00, 0x00, 0x27, 0x11]);
+///
+///     let deserialized_reply: Reply = serde_xdr::from_bytes(&bytes).unwrap();
+///
+///     assert_eq!(deserialized_reply, reply);
+/// }
+/// ```
+pub trait Union {
+    /// The type of the discriminant.
+    ///
+    /// The discriminant is serialized and deserialized using the type's own
+    /// implementations, so it can be an `i32`, an `u32`, a `bool` or an
+    /// enumeration type.
+    type Discriminant: Debug
+        + DeserializeOwned
+        + PartialEq
+        + Serialize
+        + 'static;
+
+    /// The discriminant of each variant, in the order the variants are
+    /// declared.
+    ///
+    /// The default arm has no entry, because it takes its discriminant from
+    /// its first field, so this lists the discriminants of the other variants.
+    const DISCRIMINANTS: &'static [Self::Discriminant];
+
+    /// The index of the variant that is the `default` arm of the union, if
+    /// there is one.
+    ///
+    /// The default arm is selected for any discriminant that doesn't belong to
+    /// another arm. The first field of the variant captures the discriminant,
+    /// and is written back as the discriminant when the variant is serialized,
+    /// which fails if it's the discriminant of another arm. Any other fields
+    /// are the payload of the arm.
+    const DEFAULT_ARM: Option<usize> = None;
+}
+
+/// Retrieve the discriminant of a variant that isn't the default arm.
+fn discriminant_of<T>(variant_index: usize) -> Option<&'static T::Discriminant>
+where
+    T: Union,
+{
+    let index = match T::DEFAULT_ARM {
+        Some(default_arm) if variant_index > default_arm => variant_index - 1,
+        _ => variant_index,
+    };
+
+    T::DISCRIMINANTS.get(index)
+}
+
+/// Retrieve the index of the variant selected by a discriminant.
+fn variant_of<T>(discriminant: &T::Discriminant) -> Option<usize>
+where
+    T: Union,
+{
+    let index = T::DISCRIMINANTS
+        .iter()
+        .position(|known| known == discriminant);
+
+    match (index, T::DEFAULT_ARM) {
+        (Some(index), Some(default_arm)) if index >= default_arm => {
+            Some(index + 1)
+        }
+        (Some(index), _) => Some(index),
+        (None, default_arm) => default_arm,
+    }
+}
+
+/// Serialize an enum as a discriminated union with explicit discriminants.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + Union,
+    S: Serializer,
+{
+    value.serialize(UnionSerializer::<T, S>::new(serializer))
+}
+
+/// Deserialize a discriminated union with explicit discriminants into an
+/// enum.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de> + Union,
+    D: Deserializer<'de>,
+{
+    T::deserialize(UnionDeserializer::<T, D>::new(deserializer))
+}
+
+mod captured;
+mod deserializer;
+mod serializer;
+
+#[cfg(test)]
+mod tests;
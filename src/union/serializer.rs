@@ -0,0 +1,459 @@
+use {
+    super::{captured::Captured, discriminant_of, Union},
+    crate::SerializationError,
+    serde::{
+        de::{value, Deserialize},
+        ser::{
+            self, Error, Impossible, Serialize, SerializeStructVariant,
+            SerializeTuple, SerializeTupleVariant,
+        },
+    },
+    std::{any::type_name, marker::PhantomData},
+};
+
+/// Serializer that writes an enum as its explicit discriminant followed by the
+/// fields of the variant.
+pub struct UnionSerializer<T, S>
+where
+    T: Union,
+    S: ser::Serializer,
+{
+    serializer: S,
+    _union: PhantomData<T>,
+}
+
+impl<T, S> UnionSerializer<T, S>
+where
+    T: Union,
+    S: ser::Serializer,
+{
+    pub fn new(serializer: S) -> Self {
+        UnionSerializer {
+            serializer,
+            _union: PhantomData,
+        }
+    }
+
+    fn start_arm(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        fields: usize,
+    ) -> Result<ArmSerializer<T, S::SerializeTuple>, S::Error> {
+        // The first field of the default arm is the discriminant itself
+        if T::DEFAULT_ARM == Some(variant_index as usize) {
+            if fields == 0 {
+                return Err(S::Error::custom(format!(
+                    "default union arm {}::{} must capture the discriminant",
+                    name, variant
+                )));
+            }
+
+            let tuple = self.serializer.serialize_tuple(fields)?;
+
+            return Ok(ArmSerializer::new(
+                tuple,
+                name,
+                variant_index,
+                variant,
+                true,
+            ));
+        }
+
+        let discriminant = discriminant_of::<T>(variant_index as usize)
+            .ok_or_else(|| {
+                S::Error::custom(format!(
+                    "union variant {}::{} has no discriminant",
+                    name, variant
+                ))
+            })?;
+
+        let mut tuple = self.serializer.serialize_tuple(fields + 1)?;
+
+        tuple.serialize_element(discriminant)?;
+
+        Ok(ArmSerializer::new(
+            tuple,
+            name,
+            variant_index,
+            variant,
+            false,
+        ))
+    }
+
+    fn not_an_enum<O>() -> Result<O, S::Error> {
+        Err(S::Error::custom("only enums can be serialized as unions"))
+    }
+}
+
+impl<T, S> ser::Serializer for UnionSerializer<T, S>
+where
+    T: Union,
+    S: ser::Serializer,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    type SerializeSeq = Impossible<S::Ok, S::Error>;
+    type SerializeTuple = Impossible<S::Ok, S::Error>;
+    type SerializeTupleStruct = Impossible<S::Ok, S::Error>;
+    type SerializeTupleVariant = ArmSerializer<T, S::SerializeTuple>;
+    type SerializeMap = Impossible<S::Ok, S::Error>;
+    type SerializeStruct = Impossible<S::Ok, S::Error>;
+    type SerializeStructVariant = ArmSerializer<T, S::SerializeTuple>;
+
+    fn serialize_bool(self, _: bool) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_i8(self, _: i8) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_i16(self, _: i16) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_i32(self, _: i32) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_i64(self, _: i64) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_u8(self, _: u8) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_u16(self, _: u16) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_u32(self, _: u32) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_u64(self, _: u64) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_char(self, _: char) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_str(self, _: &str) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_some<V>(self, _: &V) -> Result<S::Ok, S::Error>
+    where
+        V: ?Sized + Serialize,
+    {
+        Self::not_an_enum()
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<S::Ok, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        SerializeTupleVariant::end(self.start_arm(
+            name,
+            variant_index,
+            variant,
+            0,
+        )?)
+    }
+
+    fn serialize_newtype_struct<V>(
+        self,
+        _: &'static str,
+        _: &V,
+    ) -> Result<S::Ok, S::Error>
+    where
+        V: ?Sized + Serialize,
+    {
+        Self::not_an_enum()
+    }
+
+    fn serialize_newtype_variant<V>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &V,
+    ) -> Result<S::Ok, S::Error>
+    where
+        V: ?Sized + Serialize,
+    {
+        let mut arm = self.start_arm(name, variant_index, variant, 1)?;
+
+        SerializeTupleVariant::serialize_field(&mut arm, value)?;
+        SerializeTupleVariant::end(arm)
+    }
+
+    fn serialize_seq(
+        self,
+        _: Option<usize>,
+    ) -> Result<Self::SerializeSeq, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_tuple(
+        self,
+        _: usize,
+    ) -> Result<Self::SerializeTuple, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        length: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        self.start_arm(name, variant_index, variant, length)
+    }
+
+    fn serialize_map(
+        self,
+        _: Option<usize>,
+    ) -> Result<Self::SerializeMap, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        Self::not_an_enum()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        length: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        self.start_arm(name, variant_index, variant, length)
+    }
+}
+
+/// Serializer for the fields of a union arm, which are written after the
+/// discriminant as elements of the same tuple.
+pub struct ArmSerializer<T, S>
+where
+    T: Union,
+    S: SerializeTuple,
+{
+    tuple: S,
+    name: &'static str,
+    variant_index: u32,
+    variant: &'static str,
+    check_discriminant: bool,
+    _union: PhantomData<T>,
+}
+
+impl<T, S> ArmSerializer<T, S>
+where
+    T: Union,
+    S: SerializeTuple,
+{
+    fn new(
+        tuple: S,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        check_discriminant: bool,
+    ) -> Self {
+        ArmSerializer {
+            tuple,
+            name,
+            variant_index,
+            variant,
+            check_discriminant,
+            _union: PhantomData,
+        }
+    }
+
+    fn serialize_element<V>(&mut self, value: &V) -> Result<(), S::Error>
+    where
+        V: ?Sized + Serialize,
+    {
+        if self.check_discriminant {
+            self.check_discriminant = false;
+            self.check_captured_discriminant(value)?;
+        }
+
+        self.tuple.serialize_element(value)
+    }
+
+    /// Check that the discriminant captured by the default arm can be written
+    /// as a discriminant of the union, and that it doesn't belong to one of
+    /// the other arms, because it would be deserialized as that arm instead.
+    fn check_captured_discriminant<V>(&self, value: &V) -> Result<(), S::Error>
+    where
+        V: ?Sized + Serialize,
+    {
+        let invalid_type = || {
+            S::Error::custom(format!(
+                "default union arm {}::{} must capture an i32, an u32 or a \
+                 {} discriminant",
+                self.name,
+                self.variant,
+                type_name::<T::Discriminant>()
+            ))
+        };
+
+        let captured = Captured::capture::<_, value::Error>(value)
+            .map_err(|_| invalid_type())?;
+        let is_32_bit_integer =
+            matches!(captured, Captured::I32(_) | Captured::U32(_));
+        let discriminant =
+            T::Discriminant::deserialize(captured.clone().replay())
+                .map_err(|_: value::Error| invalid_type())?;
+
+        if !is_32_bit_integer
+            && Captured::capture::<_, value::Error>(&discriminant).ok()
+                != Some(captured)
+        {
+            return Err(invalid_type());
+        }
+
+        if T::DISCRIMINANTS.contains(&discriminant) {
+            return Err(S::Error::custom(format!(
+                "default union arm {}::{} can't capture discriminant {:?}, \
+                 because it belongs to another arm",
+                self.name, self.variant, discriminant
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, S> SerializeTupleVariant for ArmSerializer<T, S>
+where
+    T: Union,
+    S: SerializeTuple,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<V>(&mut self, value: &V) -> Result<(), S::Error>
+    where
+        V: ?Sized + Serialize,
+    {
+        self.serialize_element(value)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.tuple.end()
+    }
+}
+
+impl<T, S> SerializeStructVariant for ArmSerializer<T, S>
+where
+    T: Union,
+    S: SerializeTuple,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<V>(
+        &mut self,
+        _: &'static str,
+        value: &V,
+    ) -> Result<(), S::Error>
+    where
+        V: ?Sized + Serialize,
+    {
+        self.serialize_element(value)
+    }
+
+    fn skip_field(&mut self, field: &'static str) -> Result<(), S::Error> {
+        // The skip is forwarded to the wrapped serializer, so that the XDR
+        // serializer reports it with its own error
+        self.tuple.serialize_element(&SkippedField {
+            name: self.name,
+            variant_index: self.variant_index,
+            variant: self.variant,
+            field,
+        })?;
+
+        // Other serializers may accept the skip, but the following fields
+        // would still be misaligned
+        Err(S::Error::custom(SerializationError::StructFieldSkipped {
+            name: format!("{}::{}", self.name, self.variant),
+            field,
+        }))
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.tuple.end()
+    }
+}
+
+/// A field skipped in a struct arm, which is serialized as a struct variant
+/// that skips the same field.
+struct SkippedField {
+    name: &'static str,
+    variant_index: u32,
+    variant: &'static str,
+    field: &'static str,
+}
+
+impl Serialize for SkippedField {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut struct_variant = serializer.serialize_struct_variant(
+            self.name,
+            self.variant_index,
+            self.variant,
+            0,
+        )?;
+
+        struct_variant.skip_field(self.field)?;
+        struct_variant.end()
+    }
+}
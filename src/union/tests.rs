@@ -0,0 +1,573 @@
+use {
+    super::{
+        super::{
+            enumeration, from_bytes, to_bytes, DeserializationError,
+            SerializationError,
+        },
+        Union,
+    },
+    serde::{Deserialize, Deserializer, Serialize, Serializer},
+    std::convert::TryFrom,
+};
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Status {
+    Ok,
+    Moved(String),
+    Partial(u32, bool),
+    Failed { code: i32 },
+}
+
+impl Union for Status {
+    type Discriminant = i32;
+
+    const DISCRIMINANTS: &'static [i32] = &[0, 10001, 7, -1];
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Reply {
+    #[serde(with = "super")]
+    status: Status,
+}
+
+#[test]
+fn serialize_unit_arm() {
+    let reply = Reply { status: Status::Ok };
+
+    assert_eq!(to_bytes(&reply).unwrap(), vec![0x00, 0x00, 0x00, 0x00]);
+}
+
+#[test]
+fn serialize_newtype_arm() {
+    let reply = Reply {
+        status: Status::Moved("Hi!".to_string()),
+    };
+
+    let expected_bytes = vec![
+        0x00, 0x00, 0x27, 0x11, 0x00, 0x00, 0x00, 0x03, b'H', b'i', b'!', 0x00,
+    ];
+
+    assert_eq!(to_bytes(&reply).unwrap(), expected_bytes);
+}
+
+#[test]
+fn serialize_tuple_arm() {
+    let reply = Reply {
+        status: Status::Partial(3, true),
+    };
+
+    let expected_bytes = vec![
+        0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01,
+    ];
+
+    assert_eq!(to_bytes(&reply).unwrap(), expected_bytes);
+}
+
+#[test]
+fn serialize_struct_arm_with_negative_discriminant() {
+    let reply = Reply {
+        status: Status::Failed { code: -2 },
+    };
+
+    let expected_bytes = vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe];
+
+    assert_eq!(to_bytes(&reply).unwrap(), expected_bytes);
+}
+
+#[test]
+fn deserialize_unit_arm() {
+    let reply: Reply = from_bytes([0x00, 0x00, 0x00, 0x00]).unwrap();
+
+    assert_eq!(reply, Reply { status: Status::Ok });
+}
+
+#[test]
+fn deserialize_newtype_arm() {
+    let bytes = [
+        0x00, 0x00, 0x27, 0x11, 0x00, 0x00, 0x00, 0x03, b'H', b'i', b'!', 0x00,
+    ];
+
+    let reply: Reply = from_bytes(bytes).unwrap();
+
+    assert_eq!(
+        reply,
+        Reply {
+            status: Status::Moved("Hi!".to_string())
+        }
+    );
+}
+
+#[test]
+fn deserialize_tuple_arm() {
+    let bytes = [
+        0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01,
+    ];
+
+    let reply: Reply = from_bytes(bytes).unwrap();
+
+    assert_eq!(
+        reply,
+        Reply {
+            status: Status::Partial(3, true)
+        }
+    );
+}
+
+#[test]
+fn deserialize_struct_arm_with_negative_discriminant() {
+    let bytes = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe];
+
+    let reply: Reply = from_bytes(bytes).unwrap();
+
+    assert_eq!(
+        reply,
+        Reply {
+            status: Status::Failed { code: -2 }
+        }
+    );
+}
+
+#[test]
+fn unknown_discriminant_is_reported() {
+    let error = from_bytes::<_, Reply>([0x00, 0x00, 0x00, 0x01]).unwrap_err();
+
+    match DeserializationError::from(error).root_cause() {
+        DeserializationError::InvalidValue { value, expected } => {
+            assert_eq!(value, "discriminant 1");
+            assert_eq!(expected, "a discriminant of union Status");
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn discriminant_index_is_not_accepted() {
+    let result = from_bytes::<_, Reply>([0x00, 0x00, 0x00, 0x03]);
+
+    assert!(result.is_err());
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Annotated {
+    Plain,
+    Commented {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        comment: Option<String>,
+        value: u32,
+    },
+}
+
+impl Union for Annotated {
+    type Discriminant = i32;
+
+    const DISCRIMINANTS: &'static [i32] = &[0, 1];
+}
+
+#[test]
+fn struct_arm_with_skipped_field() {
+    let value = Wrapper(Annotated::Commented {
+        comment: Some("Hi!".to_string()),
+        value: 2,
+    });
+
+    let bytes = to_bytes(&value).unwrap();
+    let result: Wrapper<Annotated> = from_bytes(&bytes).unwrap();
+
+    assert_eq!(result, value);
+
+    let error = to_bytes(&Wrapper(Annotated::Commented {
+        comment: None,
+        value: 2,
+    }))
+    .unwrap_err();
+
+    match SerializationError::from(error).root_cause() {
+        SerializationError::StructFieldSkipped { name, field } => {
+            assert_eq!(name, "Annotated::Commented");
+            assert_eq!(*field, "comment");
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Outcome {
+    Success(u32),
+    Other(i32),
+}
+
+impl Union for Outcome {
+    type Discriminant = i32;
+
+    const DISCRIMINANTS: &'static [i32] = &[0];
+    const DEFAULT_ARM: Option<usize> = Some(1);
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Response {
+    Done,
+    Error(i32, String),
+}
+
+impl Union for Response {
+    type Discriminant = i32;
+
+    const DISCRIMINANTS: &'static [i32] = &[0];
+    const DEFAULT_ARM: Option<usize> = Some(1);
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Event {
+    Tick,
+    Unknown { kind: i32, data: u32 },
+}
+
+impl Union for Event {
+    type Discriminant = i32;
+
+    const DISCRIMINANTS: &'static [i32] = &[1];
+    const DEFAULT_ARM: Option<usize> = Some(1);
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Unit {
+    Known,
+    Default,
+}
+
+impl Union for Unit {
+    type Discriminant = i32;
+
+    const DISCRIMINANTS: &'static [i32] = &[0];
+    const DEFAULT_ARM: Option<usize> = Some(1);
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Wrapper<T>(#[serde(with = "super")] T)
+where
+    T: Serialize + for<'a> Deserialize<'a> + Union;
+
+#[test]
+fn serialize_default_arm_without_payload() {
+    let bytes = to_bytes(&Wrapper(Outcome::Other(-5))).unwrap();
+
+    assert_eq!(bytes, vec![0xff, 0xff, 0xff, 0xfb]);
+}
+
+#[test]
+fn deserialize_default_arm_without_payload() {
+    let result: Wrapper<Outcome> =
+        from_bytes([0xff, 0xff, 0xff, 0xfb]).unwrap();
+
+    assert_eq!(result, Wrapper(Outcome::Other(-5)));
+}
+
+#[test]
+fn listed_discriminant_is_not_captured_by_default_arm() {
+    let result: Wrapper<Outcome> =
+        from_bytes([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x09]).unwrap();
+
+    assert_eq!(result, Wrapper(Outcome::Success(9)));
+}
+
+#[test]
+fn serialize_default_arm_with_tuple_payload() {
+    let value = Wrapper(Response::Error(42, "Hi!".to_string()));
+
+    let expected_bytes = vec![
+        0x00, 0x00, 0x00, 0x2a, 0x00, 0x00, 0x00, 0x03, b'H', b'i', b'!', 0x00,
+    ];
+
+    assert_eq!(to_bytes(&value).unwrap(), expected_bytes);
+}
+
+#[test]
+fn deserialize_default_arm_with_tuple_payload() {
+    let bytes = [
+        0x00, 0x00, 0x00, 0x2a, 0x00, 0x00, 0x00, 0x03, b'H', b'i', b'!', 0x00,
+    ];
+
+    let result: Wrapper<Response> = from_bytes(bytes).unwrap();
+
+    assert_eq!(result, Wrapper(Response::Error(42, "Hi!".to_string())));
+}
+
+#[test]
+fn serialize_default_arm_with_struct_payload() {
+    let value = Wrapper(Event::Unknown { kind: 7, data: 3 });
+
+    let expected_bytes = vec![0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x03];
+
+    assert_eq!(to_bytes(&value).unwrap(), expected_bytes);
+}
+
+#[test]
+fn deserialize_default_arm_with_struct_payload() {
+    let bytes = [0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x03];
+
+    let result: Wrapper<Event> = from_bytes(bytes).unwrap();
+
+    assert_eq!(result, Wrapper(Event::Unknown { kind: 7, data: 3 }));
+}
+
+#[test]
+fn default_arm_without_fields_is_not_serialized() {
+    assert!(to_bytes(&Wrapper(Unit::Default)).is_err());
+}
+
+#[test]
+fn default_arm_without_fields_is_deserialized() {
+    let result: Wrapper<Unit> = from_bytes([0x00, 0x00, 0x00, 0x05]).unwrap();
+
+    assert_eq!(result, Wrapper(Unit::Default));
+}
+
+#[test]
+fn default_arm_with_listed_discriminant_is_not_serialized() {
+    assert!(to_bytes(&Wrapper(Outcome::Other(0))).is_err());
+    assert!(to_bytes(&Wrapper(Event::Unknown { kind: 1, data: 3 })).is_err());
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Narrow {
+    Known,
+    Other(u8),
+}
+
+impl Union for Narrow {
+    type Discriminant = i32;
+
+    const DISCRIMINANTS: &'static [i32] = &[0];
+    const DEFAULT_ARM: Option<usize> = Some(1);
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Named {
+    Known,
+    Other(String),
+}
+
+impl Union for Named {
+    type Discriminant = i32;
+
+    const DISCRIMINANTS: &'static [i32] = &[0];
+    const DEFAULT_ARM: Option<usize> = Some(1);
+}
+
+#[test]
+fn default_arm_with_invalid_discriminant_type_is_not_serialized() {
+    assert!(to_bytes(&Wrapper(Narrow::Other(5))).is_err());
+    assert!(to_bytes(&Wrapper(Named::Other("5".to_string()))).is_err());
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Shape {
+    Point,
+    Other(i32),
+    Circle(u32),
+}
+
+impl Union for Shape {
+    type Discriminant = i32;
+
+    const DISCRIMINANTS: &'static [i32] = &[1, 2];
+    const DEFAULT_ARM: Option<usize> = Some(1);
+}
+
+#[test]
+fn default_arm_between_other_arms() {
+    let shapes = vec![
+        Wrapper(Shape::Point),
+        Wrapper(Shape::Other(9)),
+        Wrapper(Shape::Circle(3)),
+    ];
+
+    let bytes = to_bytes(&shapes).unwrap();
+
+    let expected_bytes = vec![
+        0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x09,
+        0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03,
+    ];
+
+    assert_eq!(bytes, expected_bytes);
+
+    let result: Vec<Wrapper<Shape>> = from_bytes(&bytes).unwrap();
+
+    assert_eq!(result, shapes);
+    assert!(to_bytes(&Wrapper(Shape::Other(2))).is_err());
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Flag {
+    Unset,
+    Set(u32),
+}
+
+impl Union for Flag {
+    type Discriminant = bool;
+
+    const DISCRIMINANTS: &'static [bool] = &[false, true];
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Paint {
+    Red(u32),
+    Other(Color),
+}
+
+impl Union for Paint {
+    type Discriminant = Color;
+
+    const DISCRIMINANTS: &'static [Color] = &[Color::Red];
+    const DEFAULT_ARM: Option<usize> = Some(1);
+}
+
+#[test]
+fn serialize_union_switched_on_bool() {
+    let bytes = to_bytes(&Wrapper(Flag::Set(3))).unwrap();
+
+    assert_eq!(bytes, vec![0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x03]);
+}
+
+#[test]
+fn deserialize_union_switched_on_bool() {
+    let result: Wrapper<Flag> = from_bytes([0x00, 0x00, 0x00, 0x00]).unwrap();
+
+    assert_eq!(result, Wrapper(Flag::Unset));
+}
+
+#[test]
+fn serialize_union_switched_on_enum() {
+    let bytes = to_bytes(&vec![
+        Wrapper(Paint::Red(5)),
+        Wrapper(Paint::Other(Color::Blue)),
+    ])
+    .unwrap();
+
+    let expected_bytes = vec![
+        0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05,
+        0x00, 0x00, 0x00, 0x02,
+    ];
+
+    assert_eq!(bytes, expected_bytes);
+}
+
+#[test]
+fn deserialize_union_switched_on_enum() {
+    let bytes = [
+        0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05,
+        0x00, 0x00, 0x00, 0x01,
+    ];
+
+    let result: Vec<Wrapper<Paint>> = from_bytes(bytes).unwrap();
+
+    let expected_result =
+        vec![Wrapper(Paint::Red(5)), Wrapper(Paint::Other(Color::Green))];
+
+    assert_eq!(result, expected_result);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(i32)]
+enum Opcode {
+    Read = -3,
+    Write = 7,
+    Delete = 12,
+}
+
+impl From<Opcode> for i32 {
+    fn from(opcode: Opcode) -> i32 {
+        opcode as i32
+    }
+}
+
+impl TryFrom<i32> for Opcode {
+    type Error = i32;
+
+    fn try_from(value: i32) -> Result<Self, i32> {
+        match value {
+            -3 => Ok(Opcode::Read),
+            7 => Ok(Opcode::Write),
+            12 => Ok(Opcode::Delete),
+            other => Err(other),
+        }
+    }
+}
+
+impl Serialize for Opcode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        enumeration::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Opcode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enumeration::deserialize(deserializer)
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Request {
+    Read(u32),
+    Write,
+    Other(Opcode),
+}
+
+impl Union for Request {
+    type Discriminant = Opcode;
+
+    const DISCRIMINANTS: &'static [Opcode] = &[Opcode::Read, Opcode::Write];
+    const DEFAULT_ARM: Option<usize> = Some(2);
+}
+
+#[test]
+fn serialize_union_switched_on_enumeration() {
+    let bytes = to_bytes(&vec![
+        Wrapper(Request::Read(5)),
+        Wrapper(Request::Other(Opcode::Delete)),
+    ])
+    .unwrap();
+
+    let expected_bytes = vec![
+        0x00, 0x00, 0x00, 0x02, 0xff, 0xff, 0xff, 0xfd, 0x00, 0x00, 0x00, 0x05,
+        0x00, 0x00, 0x00, 0x0c,
+    ];
+
+    assert_eq!(bytes, expected_bytes);
+}
+
+#[test]
+fn deserialize_union_switched_on_enumeration() {
+    let bytes = [
+        0x00, 0x00, 0x00, 0x03, 0xff, 0xff, 0xff, 0xfd, 0x00, 0x00, 0x00, 0x05,
+        0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x0c,
+    ];
+
+    let result: Vec<Wrapper<Request>> = from_bytes(bytes).unwrap();
+
+    let expected_result = vec![
+        Wrapper(Request::Read(5)),
+        Wrapper(Request::Write),
+        Wrapper(Request::Other(Opcode::Delete)),
+    ];
+
+    assert_eq!(result, expected_result);
+}
+
+#[test]
+fn default_arm_of_enumeration_union_rejects_listed_enumerator() {
+    assert!(to_bytes(&Wrapper(Request::Other(Opcode::Write))).is_err());
+}